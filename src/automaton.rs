@@ -0,0 +1,277 @@
+//! Life-like cellular automata: every cell is alive or dead, and each step decides its next state
+//! from its current one and how many of its neighbors are alive.
+//!
+//! A `Topology` says which cells neighbor which (any `Fn(&C) -> Vec<C>` will do), and the rule is a
+//! predicate `Fn(alive: bool, live_neighbors: usize) -> bool`, e.g. `|alive, n| n == 3 || alive && n == 2`
+//! for Conway's Life. Two backends run them:
+//!
+//! * `Sparse` tracks only the live cells of an unbounded space, so its rule must leave a dead cell
+//!   with no live neighbors dead.
+//! * `Dense` runs a fixed, finite set of cells, with their neighbors worked out once up front.
+
+use crate::grid::{Dir, Pos, DIRS4, DIRS8};
+use num_complex::Complex;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Which cells neighbor a given one.
+pub trait Topology<C> {
+    fn neighbors(&self, cell: &C) -> Vec<C>;
+}
+
+impl<C, F> Topology<C> for F
+where
+    F: Fn(&C) -> Vec<C>,
+{
+    fn neighbors(&self, cell: &C) -> Vec<C> {
+        self(cell)
+    }
+}
+
+/// A bounded 2D grid of `(row, col)` positions.
+pub struct Square {
+    pub width: usize,
+    pub height: usize,
+    pub dirs: &'static [Dir],
+}
+
+impl Square {
+    /// With the 8 surrounding cells as neighbors.
+    pub fn moore(width: usize, height: usize) -> Square {
+        Square {
+            width,
+            height,
+            dirs: &DIRS8,
+        }
+    }
+
+    /// With only the 4 cells up, down, left and right as neighbors.
+    pub fn von_neumann(width: usize, height: usize) -> Square {
+        Square {
+            width,
+            height,
+            dirs: &DIRS4,
+        }
+    }
+}
+
+impl Topology<Pos> for Square {
+    fn neighbors(&self, &(i, j): &Pos) -> Vec<Pos> {
+        self.dirs
+            .iter()
+            .filter_map(|&(di, dj)| Some((i.checked_add_signed(di)?, j.checked_add_signed(dj)?)))
+            .filter(|&(i, j)| i < self.height && j < self.width)
+            .collect()
+    }
+}
+
+/// An unbounded N-dimensional lattice, where every cell within one step along each axis (diagonals
+/// included) is a neighbor.
+pub struct Lattice {
+    offsets: Vec<Vec<i32>>,
+}
+
+impl Lattice {
+    pub fn new(dims: usize) -> Lattice {
+        let mut offsets: Vec<Vec<i32>> = vec![vec![]];
+        for _ in 0..dims {
+            offsets = offsets
+                .iter()
+                .flat_map(|o| (-1..=1).map(move |d| o.iter().cloned().chain(Some(d)).collect()))
+                .collect();
+        }
+        offsets.retain(|o| o.iter().any(|d| *d != 0));
+        Lattice { offsets }
+    }
+
+    pub fn dims(&self) -> usize {
+        self.offsets.first().map_or(0, |o| o.len())
+    }
+}
+
+impl Topology<Vec<i32>> for Lattice {
+    fn neighbors(&self, cell: &Vec<i32>) -> Vec<Vec<i32>> {
+        self.offsets
+            .iter()
+            .map(|o| cell.iter().zip(o).map(|(x, d)| x + d).collect())
+            .collect()
+    }
+}
+
+/// An unbounded grid of hexagons with pointy tops, in "doubled" coordinates `x + yi`: east and west
+/// are 2 apart, and the diagonals are one step along each axis.
+pub struct Hex;
+
+impl Hex {
+    pub const E: Complex<i32> = Complex::new(2, 0);
+    pub const W: Complex<i32> = Complex::new(-2, 0);
+    pub const NE: Complex<i32> = Complex::new(1, 1);
+    pub const NW: Complex<i32> = Complex::new(-1, 1);
+    pub const SE: Complex<i32> = Complex::new(1, -1);
+    pub const SW: Complex<i32> = Complex::new(-1, -1);
+    pub const DIRS: [Complex<i32>; 6] = [Hex::E, Hex::W, Hex::NE, Hex::NW, Hex::SE, Hex::SW];
+}
+
+impl Topology<Complex<i32>> for Hex {
+    fn neighbors(&self, cell: &Complex<i32>) -> Vec<Complex<i32>> {
+        Hex::DIRS.iter().map(|d| cell + d).collect()
+    }
+}
+
+/// A way of running an automaton.
+pub trait Automaton {
+    /// Advance one generation, returning whether any cell changed.
+    fn step(&mut self) -> bool;
+
+    /// How many cells are alive.
+    fn population(&self) -> usize;
+
+    fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Step until nothing changes any more, returning how many steps that took.
+    fn settle(&mut self) -> usize {
+        let mut steps = 0;
+        while self.step() {
+            steps += 1;
+        }
+        steps
+    }
+}
+
+/// Only the live cells, in a space without bounds.
+pub struct Sparse<C, T, R> {
+    topology: T,
+    rule: R,
+    alive: HashSet<C>,
+}
+
+impl<C, T, R> Sparse<C, T, R>
+where
+    C: Clone + Eq + Hash,
+    T: Topology<C>,
+    R: Fn(bool, usize) -> bool,
+{
+    pub fn new<I>(topology: T, rule: R, alive: I) -> Sparse<C, T, R>
+    where
+        I: IntoIterator<Item = C>,
+    {
+        Sparse {
+            topology,
+            rule,
+            alive: alive.into_iter().collect(),
+        }
+    }
+
+    pub fn alive(&self) -> &HashSet<C> {
+        &self.alive
+    }
+}
+
+impl<C, T, R> Automaton for Sparse<C, T, R>
+where
+    C: Clone + Eq + Hash,
+    T: Topology<C>,
+    R: Fn(bool, usize) -> bool,
+{
+    fn step(&mut self) -> bool {
+        // Only live cells and their neighbors can be alive next time.
+        let mut counts: HashMap<C, usize> = self.alive.iter().map(|c| (c.clone(), 0)).collect();
+        for cell in self.alive.iter() {
+            for n in self.topology.neighbors(cell) {
+                *counts.entry(n).or_insert(0) += 1;
+            }
+        }
+        let next: HashSet<C> = counts
+            .into_iter()
+            .filter(|(c, n)| (self.rule)(self.alive.contains(c), *n))
+            .map(|(c, _)| c)
+            .collect();
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    fn population(&self) -> usize {
+        self.alive.len()
+    }
+}
+
+/// A fixed set of cells. Neighbors outside the set are ignored, as if always dead.
+pub struct Dense<C, R> {
+    cells: Vec<C>,
+    index: HashMap<C, usize>,
+    neighbors: Vec<Vec<usize>>,
+    alive: Vec<bool>,
+    rule: R,
+}
+
+impl<C, R> Dense<C, R>
+where
+    C: Clone + Eq + Hash,
+    R: Fn(bool, usize) -> bool,
+{
+    /// `cells` are the cells of the space, each with whether it starts out alive.
+    pub fn new<T, I>(topology: &T, rule: R, cells: I) -> Dense<C, R>
+    where
+        T: Topology<C>,
+        I: IntoIterator<Item = (C, bool)>,
+    {
+        let (cells, alive): (Vec<C>, Vec<bool>) = cells.into_iter().unzip();
+        let index: HashMap<C, usize> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.clone(), i))
+            .collect();
+        let neighbors = cells
+            .iter()
+            .map(|c| {
+                topology
+                    .neighbors(c)
+                    .iter()
+                    .filter_map(|n| index.get(n).cloned())
+                    .collect()
+            })
+            .collect();
+        Dense {
+            cells,
+            index,
+            neighbors,
+            alive,
+            rule,
+        }
+    }
+
+    pub fn is_alive(&self, cell: &C) -> bool {
+        self.index.get(cell).is_some_and(|i| self.alive[*i])
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (&C, bool)> {
+        self.cells.iter().zip(self.alive.iter().cloned())
+    }
+}
+
+impl<C, R> Automaton for Dense<C, R>
+where
+    R: Fn(bool, usize) -> bool,
+{
+    fn step(&mut self) -> bool {
+        let alive = &self.alive;
+        let next: Vec<bool> = self
+            .neighbors
+            .iter()
+            .enumerate()
+            .map(|(i, ns)| (self.rule)(alive[i], ns.iter().filter(|n| alive[**n]).count()))
+            .collect();
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    fn population(&self) -> usize {
+        self.alive.iter().filter(|a| **a).count()
+    }
+}
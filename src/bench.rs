@@ -0,0 +1,223 @@
+use crate::differential::Outcome;
+use crate::registry::Solution;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+pub struct Config {
+    pub warmup: usize,
+    pub samples: usize,
+    // Stop sampling a solver early once it has used this much time, e.g. for day15 part2 (~3s a run).
+    pub budget: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            warmup: 3,
+            samples: 30,
+            budget: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Timing statistics for a single solver, in nanoseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measurement {
+    // Reports from before the crate was split into seasons only had 2020.
+    #[serde(default = "first_year")]
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<String>,
+    pub samples: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p90_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+    pub mean_ns: u64,
+}
+
+fn first_year() -> u32 {
+    2020
+}
+
+impl Measurement {
+    fn from_samples(solution: &Solution, mut samples: Vec<Duration>) -> Measurement {
+        samples.sort();
+        let ns: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        Measurement {
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            variant: solution.variant.map(|v| v.to_string()),
+            samples: ns.len(),
+            min_ns: ns[0],
+            median_ns: percentile(&ns, 50.0),
+            p90_ns: percentile(&ns, 90.0),
+            p99_ns: percentile(&ns, 99.0),
+            max_ns: ns[ns.len() - 1],
+            mean_ns: ns.iter().sum::<u64>() / ns.len() as u64,
+        }
+    }
+
+    fn key(&self) -> (u32, u32, u32, Option<&str>) {
+        (self.year, self.day, self.part, self.variant.as_deref())
+    }
+
+    fn name(&self) -> String {
+        match &self.variant {
+            Some(v) => format!("{} Day {} - Part {} - {}", self.year, self.day, self.part, v),
+            None => format!("{} Day {} - Part {}", self.year, self.day, self.part),
+        }
+    }
+}
+
+/// Nearest-rank percentile of already sorted values.
+pub fn percentile(sorted: &[u64], p: f64) -> u64 {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Time `solution` on `input`, after discarding `config.warmup` runs. A run that doesn't solve the
+/// puzzle, by rejecting the input or panicking, stops the measurement and is returned instead.
+pub fn measure(solution: &Solution, input: &str, config: &Config) -> Result<Measurement, Outcome> {
+    let run = || match Outcome::of(solution, black_box(input)) {
+        Outcome::Solved(answer) => Ok(black_box(answer)),
+        failed => Err(failed),
+    };
+    let start = Instant::now();
+    for _ in 0..config.warmup {
+        run()?;
+        if start.elapsed() > config.budget {
+            break;
+        }
+    }
+    let start = Instant::now();
+    let mut samples: Vec<Duration> = Vec::with_capacity(config.samples);
+    while samples.len() < config.samples.max(1) {
+        let run_start = Instant::now();
+        run()?;
+        samples.push(run_start.elapsed());
+        if start.elapsed() > config.budget {
+            break;
+        }
+    }
+    Ok(Measurement::from_samples(solution, samples))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Report> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("year,day,part,variant,samples,min_ns,median_ns,p90_ns,p99_ns,max_ns,mean_ns\n");
+        for m in self.measurements.iter() {
+            csv += &format!(
+                "{},{},{},{},{},{},{},{},{},{},{}\n",
+                m.year,
+                m.day,
+                m.part,
+                m.variant.as_deref().unwrap_or(""),
+                m.samples,
+                m.min_ns,
+                m.median_ns,
+                m.p90_ns,
+                m.p99_ns,
+                m.max_ns,
+                m.mean_ns
+            );
+        }
+        csv
+    }
+
+    /// Compare median timings against a previous report, flagging anything slower by more than
+    /// `threshold` (e.g. 0.1 for 10%).
+    pub fn compare(&self, baseline: &Report, threshold: f64) -> Vec<Comparison> {
+        self.measurements
+            .iter()
+            .filter_map(|m| {
+                let base = baseline.measurements.iter().find(|b| b.key() == m.key())?;
+                let ratio = m.median_ns as f64 / base.median_ns.max(1) as f64;
+                Some(Comparison {
+                    name: m.name(),
+                    baseline_ns: base.median_ns,
+                    current_ns: m.median_ns,
+                    ratio,
+                    regressed: ratio > 1.0 + threshold,
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in self.measurements.iter() {
+            writeln!(
+                f,
+                "{}: median {:?}, p90 {:?}, p99 {:?} ({} samples)",
+                m.name(),
+                Duration::from_nanos(m.median_ns),
+                Duration::from_nanos(m.p90_ns),
+                Duration::from_nanos(m.p99_ns),
+                m.samples
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Comparison {
+    pub name: String,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+    pub ratio: f64,
+    pub regressed: bool,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:?} -> {:?} ({:+.1}%){}",
+            self.name,
+            Duration::from_nanos(self.baseline_ns),
+            Duration::from_nanos(self.current_ns),
+            (self.ratio - 1.0) * 100.0,
+            if self.regressed { " REGRESSED" } else { "" }
+        )
+    }
+}
+
+/// Benchmark each of `solutions` on the input returned by `read_input` for its year and day.
+///
+/// Solvers whose input is missing or fails to parse are skipped, and reported in the second list.
+pub fn run<'a, I, F>(solutions: I, read_input: F, config: &Config) -> (Report, Vec<(&'a Solution, String)>)
+where
+    I: IntoIterator<Item = &'a Solution>,
+    F: Fn(u32, u32) -> Option<String>,
+{
+    let mut report = Report::default();
+    let mut skipped = Vec::new();
+    for s in solutions {
+        match read_input(s.year, s.day).map(|input| measure(s, &input, config)) {
+            Some(Ok(m)) => report.measurements.push(m),
+            Some(Err(e)) => skipped.push((s, e.to_string())),
+            None => skipped.push((s, "no input".to_string())),
+        }
+    }
+    (report, skipped)
+}
@@ -0,0 +1,111 @@
+use crate::error::ParseError;
+use crate::registry::{self, Answer, Solution, Solver};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+// Inputs are often thousands of lines, so only echo the start of them in a report.
+const MAX_INPUT_LINES: usize = 20;
+
+/// What a single variant did with the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    Rejected(ParseError),
+    Panicked(String),
+}
+
+impl Outcome {
+    pub fn of(solution: &Solution, input: &str) -> Outcome {
+        match panic::catch_unwind(AssertUnwindSafe(|| solution.solve(input))) {
+            Ok(Ok(answer)) => Outcome::Solved(answer),
+            Ok(Err(e)) => Outcome::Rejected(e),
+            Err(payload) => Outcome::Panicked(
+                payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "<unknown panic>".to_string()),
+            ),
+        }
+    }
+
+    // Two variants agree if they produce the same answer, or fail in the same way.
+    fn agrees_with(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (Outcome::Solved(a), Outcome::Solved(b)) => a == b,
+            (Outcome::Rejected(_), Outcome::Rejected(_)) => true,
+            (Outcome::Panicked(_), Outcome::Panicked(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Solved(answer) => write!(f, "{}", answer),
+            Outcome::Rejected(e) => write!(f, "rejected: {}", e.to_string().lines().next().unwrap_or("")),
+            Outcome::Panicked(msg) => write!(f, "panicked: {}", msg),
+        }
+    }
+}
+
+/// The result of running every variant of a day/part against the same input.
+pub struct Report {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub outcomes: Vec<(&'static Solution, Outcome)>,
+}
+
+impl Report {
+    pub fn agrees(&self) -> bool {
+        match self.outcomes.first() {
+            Some((_, first)) => self.outcomes.iter().all(|(_, o)| o.agrees_with(first)),
+            None => true,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.agrees() { "variants agree" } else { "variants DISAGREE" };
+        writeln!(f, "{} Day {} - Part {}: {}", self.year, self.day, self.part, verdict)?;
+        for (solution, outcome) in self.outcomes.iter() {
+            writeln!(f, "\t{:<14} {}", solution.variant.unwrap_or("(default)"), outcome)?;
+        }
+        if !self.agrees() {
+            writeln!(f, "\tinput:")?;
+            for line in self.input.lines().take(MAX_INPUT_LINES) {
+                writeln!(f, "\t| {}", line)?;
+            }
+            let more = self.input.lines().count().saturating_sub(MAX_INPUT_LINES);
+            if more > 0 {
+                writeln!(f, "\t| ... ({} more lines)", more)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run every registered variant of `day`/`part` on `input`.
+pub fn check(year: u32, day: u32, part: u32, input: &str) -> Report {
+    Report {
+        year,
+        day,
+        part,
+        input: input.to_string(),
+        outcomes: registry::variants(year, day, part)
+            .map(|s| (s, Outcome::of(s, input)))
+            .collect(),
+    }
+}
+
+/// Check every part of `day` that has more than one variant.
+pub fn check_day(year: u32, day: u32, input: &str) -> Vec<Report> {
+    (1..=2)
+        .filter(|part| registry::variants(year, day, *part).count() > 1)
+        .map(|part| check(year, day, part, input))
+        .collect()
+}
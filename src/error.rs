@@ -0,0 +1,119 @@
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed puzzle input, pinned to the line and column that tripped up the parser.
+#[derive(Clone, PartialEq, Eq, Serialize)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,   // 1-indexed
+    pub column: usize, // 1-indexed, in chars
+    pub text: String,  // The full offending line.
+    pub span: usize,   // Number of chars to underline starting at `column`.
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    // day 8, line 3, column 1: unknown operator `jmq`
+    //    3 | jmq +4
+    //      | ^^^
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        let gutter = self.line.to_string();
+        writeln!(f, "{} | {}", gutter, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            " ".repeat(gutter.len()),
+            " ".repeat(self.column - 1),
+            "^".repeat(self.span.max(1))
+        )
+    }
+}
+
+// aoc_runner reports failures with `{:#?}`, so make that readable too.
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for ParseError {}
+
+/// The raw input for a given day, used to turn a bad fragment of it into a `ParseError`.
+///
+/// Fragments must be slices of `input` (e.g. from `lines()`, `split()` or regex captures),
+/// since their position is recovered from their address.
+#[derive(Clone, Copy)]
+pub struct Source<'a> {
+    pub day: u32,
+    pub input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u32, input: &'a str) -> Source<'a> {
+        Source { day, input }
+    }
+
+    pub fn error(&self, fragment: &str, message: impl Into<String>) -> ParseError {
+        let offset = self.offset_of(fragment);
+        let line_start = self.input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.input[offset..]
+            .find('\n')
+            .map_or(self.input.len(), |i| offset + i);
+        ParseError {
+            day: self.day,
+            line: self.input[..line_start].matches('\n').count() + 1,
+            column: self.input[line_start..offset].chars().count() + 1,
+            text: self.input[line_start..line_end].to_string(),
+            span: fragment.lines().next().map_or(0, |l| l.chars().count()),
+            message: message.into(),
+        }
+    }
+
+    /// An error for input that ended before the parser found what it needed.
+    pub fn missing(&self, message: impl Into<String>) -> ParseError {
+        self.error(&self.input[self.input.len()..], message)
+    }
+
+    pub fn parse<T>(&self, fragment: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        fragment
+            .parse::<T>()
+            .map_err(|e| self.error(fragment, format!("invalid number `{}`: {}", fragment, e)))
+    }
+
+    pub fn captures(&self, re: &Regex, fragment: &'a str, expected: &str) -> Result<Captures<'a>, ParseError> {
+        re.captures(fragment)
+            .ok_or_else(|| self.error(fragment, format!("expected {}", expected)))
+    }
+
+    /// Parse every line of the input as a single number.
+    pub fn numbers<T>(&self) -> impl Iterator<Item = Result<T, ParseError>> + 'a
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let src = *self;
+        self.input.lines().map(move |l| src.parse::<T>(l))
+    }
+
+    fn offset_of(&self, fragment: &str) -> usize {
+        let start = self.input.as_ptr() as usize;
+        let addr = fragment.as_ptr() as usize;
+        if addr < start || addr > start + self.input.len() {
+            // Not a slice of our input, so the best we can do is point at the start.
+            return 0;
+        }
+        addr - start
+    }
+}
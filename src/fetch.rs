@@ -0,0 +1,80 @@
+use crate::input;
+use crate::remote::{Remote, RemoteError};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Puzzle inputs, read from the local cache (the usual `input/` directory) where possible and
+/// downloaded into it otherwise.
+pub struct Fetcher {
+    pub remote: Remote,
+    pub cache_dir: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The input is already cached here, so there's no reason to ask the server again.
+    Cached(PathBuf),
+    Remote(RemoteError),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Cached(path) => write!(f, "already have {}; delete it to fetch again", path.display()),
+            FetchError::Remote(e) => write!(f, "could not fetch input: {}", e),
+            FetchError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl From<RemoteError> for FetchError {
+    fn from(e: RemoteError) -> FetchError {
+        FetchError::Remote(e)
+    }
+}
+
+impl Fetcher {
+    pub fn new(remote: Remote, cache_dir: PathBuf) -> Fetcher {
+        Fetcher { remote, cache_dir }
+    }
+
+    /// Caches into `input/`, configured from the environment (see `Remote::from_env`).
+    pub fn from_env() -> Fetcher {
+        Fetcher::new(Remote::from_env(), PathBuf::from(input::INPUT_DIR))
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        input::path_in(&self.cache_dir, year, day)
+    }
+
+    pub fn cached(&self, year: u32, day: u32) -> Option<String> {
+        fs::read_to_string(self.path(year, day)).ok()
+    }
+
+    /// The input for a day, downloading it only if it isn't cached yet.
+    pub fn get(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        match self.cached(year, day) {
+            Some(input) => Ok(input),
+            None => self.fetch(year, day),
+        }
+    }
+
+    /// Download the input for a day into the cache. Refuses if there's a cached copy already.
+    pub fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return Err(FetchError::Cached(path));
+        }
+        let input = self.remote.get(&format!("/{}/day/{}/input", year, day))?;
+        let io_error = |e| FetchError::Io(path.clone(), e);
+        fs::create_dir_all(path.parent().unwrap()).map_err(io_error)?;
+        // Write to the side first, so that an interrupted download doesn't look cached.
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, &input).map_err(io_error)?;
+        fs::rename(&partial, &path).map_err(io_error)?;
+        Ok(input)
+    }
+}
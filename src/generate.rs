@@ -0,0 +1,99 @@
+//! Synthesize structurally valid puzzle inputs, for stress-testing the solvers beyond the single
+//! checked-in input per day.
+//!
+//! Where the shape of the input makes it cheap, generators also plant (or independently compute)
+//! the expected answer, so the output can be checked without trusting any solver.
+
+use crate::registry::Answer;
+
+mod y2020;
+
+/// A small, seedable PRNG (SplitMix64), so that a failing input can be regenerated from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `lo..hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo < hi, "empty range {}..{}", lo, hi);
+        lo + (self.next_u64() % (hi - lo) as u64) as i64
+    }
+
+    /// Uniform in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        self.range(0, n as i64) as usize
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// A random lowercase word, e.g. for ingredient names.
+    pub fn word(&mut self, min_len: usize, max_len: usize) -> String {
+        let len = self.range(min_len as i64, max_len as i64 + 1) as usize;
+        (0..len).map(|_| (b'a' + self.below(26) as u8) as char).collect()
+    }
+}
+
+pub struct Generated {
+    pub input: String,
+    /// (part, answer) for each part whose answer is known by construction.
+    pub answers: Vec<(u32, Answer)>,
+}
+
+impl Generated {
+    pub fn new(input: String) -> Generated {
+        Generated { input, answers: Vec::new() }
+    }
+
+    pub fn with_answer<A: Into<Answer>>(mut self, part: u32, answer: A) -> Generated {
+        self.answers.push((part, answer.into()));
+        self
+    }
+
+    pub fn answer(&self, part: u32) -> Option<&Answer> {
+        self.answers.iter().find(|(p, _)| *p == part).map(|(_, a)| a)
+    }
+}
+
+/// Roughly the size of the real inputs, in whatever unit each day's generator counts, or 0 if
+/// there is no generator for the day.
+pub fn default_size(year: u32, day: u32) -> usize {
+    match year {
+        2020 => y2020::default_size(day),
+        _ => 0,
+    }
+}
+
+/// Generate an input for a day, or None if there is no generator for it.
+pub fn generate(year: u32, day: u32, size: usize, rng: &mut Rng) -> Option<Generated> {
+    let size = size.max(1);
+    match year {
+        2020 => y2020::generate(day, size, rng),
+        _ => None,
+    }
+}
@@ -0,0 +1,91 @@
+//! Generators for the 2020 puzzles.
+
+use super::{Generated, Rng};
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+pub fn default_size(day: u32) -> usize {
+    match day {
+        1 => 200,
+        2 => 1000,
+        3 => 323,
+        4 => 290,
+        5 => 800,
+        6 => 480,
+        7 => 590,
+        8 => 600,
+        9 => 1000,
+        10 => 100,
+        11 => 90,
+        12 => 780,
+        13 => 9,
+        14 => 100,
+        15 => 6,
+        16 => 240,
+        17 => 8,
+        18 => 370,
+        19 => 450,
+        20 => 12,
+        21 => 40,
+        22 => 25,
+        23 => 9,
+        24 => 500,
+        25 => 1_000_000,
+        _ => 0,
+    }
+}
+
+pub fn generate(day: u32, size: usize, rng: &mut Rng) -> Option<Generated> {
+    Some(match day {
+        1 => day1::generate(size, rng),
+        2 => day2::generate(size, rng),
+        3 => day3::generate(size, rng),
+        4 => day4::generate(size, rng),
+        5 => day5::generate(size, rng),
+        6 => day6::generate(size, rng),
+        7 => day7::generate(size, rng),
+        8 => day8::generate(size, rng),
+        9 => day9::generate(size, rng),
+        10 => day10::generate(size, rng),
+        11 => day11::generate(size, rng),
+        12 => day12::generate(size, rng),
+        13 => day13::generate(size, rng),
+        14 => day14::generate(size, rng),
+        15 => day15::generate(size, rng),
+        16 => day16::generate(size, rng),
+        17 => day17::generate(size, rng),
+        18 => day18::generate(size, rng),
+        19 => day19::generate(size, rng),
+        20 => day20::generate(size, rng),
+        21 => day21::generate(size, rng),
+        22 => day22::generate(size, rng),
+        23 => day23::generate(size, rng),
+        24 => day24::generate(size, rng),
+        25 => day25::generate(size, rng),
+        _ => return None,
+    })
+}
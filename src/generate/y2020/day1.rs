@@ -0,0 +1,45 @@
+use super::{Generated, Rng};
+use std::collections::HashSet;
+
+const TARGET: i64 = 2020;
+
+// Plants exactly one pair and one triple summing to 2020, with no other way to get there even
+// reusing an entry. Every other entry is above 1010, so it can only reach 2020 together with
+// planted entries, which we check for as we go.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let (a, b, x, y, z) = loop {
+        let a = rng.range(1, TARGET / 2);
+        let x = rng.range(1, TARGET / 3);
+        let y = rng.range(1, TARGET / 3);
+        let planted = [a, TARGET - a, x, y, TARGET - x - y];
+        let distinct: HashSet<i64> = planted.iter().cloned().collect();
+        let pairs = sums(&planted, 2).filter(|s| *s == TARGET).count();
+        let triples = sums(&planted, 3).filter(|s| *s == TARGET).count();
+        if distinct.len() == 5 && pairs == 1 && triples == 1 {
+            break (planted[0], planted[1], planted[2], planted[3], planted[4]);
+        }
+    };
+    let smalls = [a, x, y, z];
+    let mut values: HashSet<i64> = [a, b, x, y, z].iter().cloned().collect();
+    let upper = TARGET + 4 * size as i64;
+    while values.len() < size.max(5) {
+        let v = rng.range(TARGET / 2 + 1, upper);
+        if v >= TARGET || !sums(&smalls, 1).chain(sums(&smalls, 2)).any(|s| v + s == TARGET) {
+            values.insert(v);
+        }
+    }
+    let mut values: Vec<i64> = values.into_iter().collect();
+    rng.shuffle(&mut values);
+    let input = values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join("\n");
+    Generated::new(input)
+        .with_answer(1, a * b)
+        .with_answer(2, x * y * z)
+}
+
+// Every sum of `k` entries of `values`, allowing an entry to be used more than once.
+fn sums(values: &[i64], k: usize) -> Box<dyn Iterator<Item = i64> + '_> {
+    if k == 0 {
+        return Box::new(std::iter::once(0));
+    }
+    Box::new((0..values.len()).flat_map(move |i| sums(&values[i..], k - 1).map(move |s| s + values[i])))
+}
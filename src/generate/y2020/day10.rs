@@ -0,0 +1,47 @@
+use super::{Generated, Rng};
+
+// Joltage steps are mostly 1 or 3, as in the real input; long runs of 1s multiply the number of
+// arrangements, so cap them.
+const MAX_ONES: usize = 4;
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut adapters: Vec<i32> = Vec::with_capacity(size);
+    let (mut ones, mut threes, mut run) = (0, 1, 0);
+    let mut joltage = 0;
+    for _ in 0..size {
+        let step = if run >= MAX_ONES || rng.chance(0.3) {
+            3
+        } else if rng.chance(0.05) {
+            2
+        } else {
+            1
+        };
+        match step {
+            1 => {
+                ones += 1;
+                run += 1;
+            }
+            3 => {
+                threes += 1;
+                run = 0;
+            }
+            _ => run = 0,
+        }
+        joltage += step;
+        adapters.push(joltage);
+    }
+    // ways[j] is the number of arrangements reaching joltage j; None once it overflows.
+    let mut ways: Vec<Option<i64>> = vec![Some(0); joltage as usize + 1];
+    ways[0] = Some(1);
+    for a in adapters.iter() {
+        let j = *a as usize;
+        ways[j] = (1..=3.min(j)).try_fold(0i64, |sum, d| sum.checked_add(ways[j - d]?));
+    }
+    rng.shuffle(&mut adapters);
+    let input = adapters.iter().map(|v| v.to_string()).collect::<Vec<String>>().join("\n");
+    let generated = Generated::new(input).with_answer(1, ones * threes);
+    match ways[joltage as usize] {
+        Some(w) => generated.with_answer(2, w),
+        None => generated,
+    }
+}
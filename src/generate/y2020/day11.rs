@@ -0,0 +1,16 @@
+use super::{Generated, Rng};
+
+const WIDTH: usize = 90;
+
+// Only the layout is random: the seating answers come from simulation, so no answer is planted.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let input = (0..size)
+        .map(|_| {
+            (0..WIDTH)
+                .map(|_| if rng.chance(0.8) { 'L' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    Generated::new(input)
+}
@@ -0,0 +1,47 @@
+use super::{Generated, Rng};
+
+// Rotate (x, y) counter-clockwise by `quarters` quarter turns.
+fn rotate((x, y): (i32, i32), quarters: i32) -> (i32, i32) {
+    (0..quarters.rem_euclid(4)).fold((x, y), |(x, y), _| (-y, x))
+}
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut lines = Vec::with_capacity(size);
+    // Part 1 moves the ship in a heading; part 2 moves a waypoint relative to the ship.
+    let (mut ship, mut heading) = ((0, 0), (1, 0));
+    let (mut ship2, mut waypoint) = ((0, 0), (10, 1));
+    for _ in 0..size {
+        let act = *rng.pick(&['N', 'S', 'E', 'W', 'L', 'R', 'F', 'F']);
+        let val = match act {
+            'L' | 'R' => 90 * rng.range(1, 4) as i32,
+            'F' => rng.range(1, 100) as i32,
+            _ => rng.range(1, 6) as i32,
+        };
+        let unit = match act {
+            'N' => (0, 1),
+            'S' => (0, -1),
+            'E' => (1, 0),
+            'W' => (-1, 0),
+            _ => (0, 0),
+        };
+        match act {
+            'L' | 'R' => {
+                let quarters = if act == 'L' { val / 90 } else { -val / 90 };
+                heading = rotate(heading, quarters);
+                waypoint = rotate(waypoint, quarters);
+            }
+            'F' => {
+                ship = (ship.0 + heading.0 * val, ship.1 + heading.1 * val);
+                ship2 = (ship2.0 + waypoint.0 * val, ship2.1 + waypoint.1 * val);
+            }
+            _ => {
+                ship = (ship.0 + unit.0 * val, ship.1 + unit.1 * val);
+                waypoint = (waypoint.0 + unit.0 * val, waypoint.1 + unit.1 * val);
+            }
+        }
+        lines.push(format!("{}{}", act, val));
+    }
+    Generated::new(lines.join("\n"))
+        .with_answer(1, ship.0.abs() + ship.1.abs())
+        .with_answer(2, ship2.0.abs() + ship2.1.abs())
+}
@@ -0,0 +1,65 @@
+use super::{Generated, Rng};
+
+// The fast solver's modular arithmetic needs headroom above the product of the bus ids.
+const MAX_PRODUCT: i64 = 1_000_000_000_000_000;
+
+fn is_prime(n: i64) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let primes: Vec<i64> = (7..1000).filter(|p| is_prime(*p)).collect();
+    let mut buses: Vec<i64> = Vec::with_capacity(size);
+    let mut product: i64 = 1;
+    while buses.len() < size {
+        let p = *rng.pick(&primes);
+        if buses.contains(&p) {
+            continue;
+        }
+        if product.saturating_mul(p) > MAX_PRODUCT {
+            break;
+        }
+        product *= p;
+        buses.push(p);
+    }
+    // Spread the buses out over the schedule, with the first one departing at offset 0.
+    let mut schedule: Vec<Option<i64>> = Vec::new();
+    for (i, b) in buses.iter().enumerate() {
+        if i > 0 {
+            schedule.extend((0..rng.below(20)).map(|_| None));
+        }
+        schedule.push(Some(*b));
+    }
+    // The solver assumes nothing departs exactly at the earliest timestamp.
+    let earliest = loop {
+        let t = rng.range(100_000, 10_000_000);
+        if buses.iter().all(|b| t % b != 0) {
+            break t;
+        }
+    };
+    let (wait, bus) = buses.iter().map(|b| (b - earliest % b, *b)).min().unwrap();
+
+    // Sieve for the earliest t where each bus departs at t + its offset.
+    let (mut t, mut step) = (0i128, 1i128);
+    for (i, b) in schedule.iter().enumerate() {
+        if let Some(b) = b {
+            while (t + i as i128) % *b as i128 != 0 {
+                t += step;
+            }
+            step *= *b as i128;
+        }
+    }
+
+    let input = format!(
+        "{}\n{}",
+        earliest,
+        schedule
+            .iter()
+            .map(|b| b.map_or("x".to_string(), |b| b.to_string()))
+            .collect::<Vec<String>>()
+            .join(",")
+    );
+    Generated::new(input)
+        .with_answer(1, wait * bus)
+        .with_answer(2, t as i64)
+}
@@ -0,0 +1,41 @@
+use super::{Generated, Rng};
+use std::collections::HashMap;
+
+const BITS: usize = 36;
+// Each X doubles the addresses written in part 2, so keep them as sparse as the real input's.
+const MAX_FLOATING: usize = 9;
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut lines = Vec::new();
+    let mut v1: HashMap<u64, u64> = HashMap::new();
+    let mut v2: HashMap<u64, u64> = HashMap::new();
+    for _ in 0..size {
+        let floating = rng.below(MAX_FLOATING + 1);
+        let mut mask: Vec<char> = (0..BITS).map(|i| if i < floating { 'X' } else { *rng.pick(&['0', '1']) }).collect();
+        rng.shuffle(&mut mask);
+        let bits = |c: char| -> u64 {
+            mask.iter().fold(0, |acc, m| acc << 1 | (*m == c) as u64)
+        };
+        let (ones, xs) = (bits('1'), bits('X'));
+        lines.push(format!("mask = {}", mask.iter().collect::<String>()));
+        for _ in 0..rng.range(1, 6) {
+            let addr = rng.range(0, 1 << 16) as u64;
+            let value = rng.range(0, 1 << 30) as u64;
+            lines.push(format!("mem[{}] = {}", addr, value));
+            v1.insert(addr, value & xs | ones);
+            // Walk every subset of the floating bits.
+            let base = (addr | ones) & !xs;
+            let mut sub = xs;
+            loop {
+                v2.insert(base | sub, value);
+                if sub == 0 {
+                    break;
+                }
+                sub = (sub - 1) & xs;
+            }
+        }
+    }
+    Generated::new(lines.join("\n"))
+        .with_answer(1, v1.values().sum::<u64>())
+        .with_answer(2, v2.values().sum::<u64>())
+}
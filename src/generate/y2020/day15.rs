@@ -0,0 +1,23 @@
+use super::{Generated, Rng};
+
+const TURNS: usize = 2020;
+
+// Part 2 takes 30 million turns, which is the solver's job; part 1 is cheap enough to plant.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut seed: Vec<usize> = (0..size.max(2) * 3).collect();
+    rng.shuffle(&mut seed);
+    seed.truncate(size.max(2));
+    // last_spoken[n] is the turn n was last spoken, if ever (ignoring the most recent turn).
+    let mut last_spoken: Vec<Option<usize>> = vec![None; TURNS + seed.len() * 3];
+    let mut spoken = seed[0];
+    for turn in 1..TURNS {
+        let next = match seed.get(turn) {
+            Some(n) => *n,
+            None => last_spoken[spoken].map_or(0, |t| turn - 1 - t),
+        };
+        last_spoken[spoken] = Some(turn - 1);
+        spoken = next;
+    }
+    let input = seed.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(",");
+    Generated::new(input).with_answer(1, spoken)
+}
@@ -0,0 +1,76 @@
+use super::{Generated, Rng};
+use std::collections::HashSet;
+
+const FIELDS: usize = 20;
+const DEPARTURES: usize = 6;
+// Ticket values start at BASE, so the gap in each rule (somewhere below it) never matters.
+const BASE: i32 = 50;
+const BAND: i32 = 10;
+
+// Fields are ranked, and the field of rank r accepts values up to the top of band r. A column
+// holding values from band r (and below) fits every field of rank >= r, so there is exactly one
+// assignment, and it can be found by repeatedly taking the field with a single candidate column.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let top = |rank: usize| BASE + BAND * (rank as i32 + 1) - 1;
+    let mut names: Vec<String> = Vec::with_capacity(FIELDS);
+    let mut seen: HashSet<String> = HashSet::new();
+    while names.len() < FIELDS {
+        let word = rng.word(4, 8);
+        let name = if names.len() < DEPARTURES { format!("departure {}", word) } else { word };
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    // rank_of_field[f] and field_at_column[c] are independent shuffles.
+    let mut rank_of_field: Vec<usize> = (0..FIELDS).collect();
+    rng.shuffle(&mut rank_of_field);
+    let mut field_at_column: Vec<usize> = (0..FIELDS).collect();
+    rng.shuffle(&mut field_at_column);
+
+    let mut rules: Vec<String> = (0..FIELDS)
+        .map(|f| {
+            let gap = rng.range(2, BASE as i64 - 2) as i32;
+            format!("{}: 1-{} or {}-{}", names[f], gap - 1, gap + 1, top(rank_of_field[f]))
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+
+    let ticket = |rng: &mut Rng| -> Vec<i32> {
+        field_at_column
+            .iter()
+            .map(|f| rng.range(BASE as i64, top(rank_of_field[*f]) as i64 + 1) as i32)
+            .collect()
+    };
+    let mine = ticket(rng);
+    let mut nearby: Vec<Vec<i32>> = Vec::with_capacity(size.max(FIELDS));
+    let mut error_rate = 0;
+    for i in 0..size.max(FIELDS) {
+        let mut t = ticket(rng);
+        if i < FIELDS {
+            // Make sure each column has a value from the top of its band.
+            let rank = rank_of_field[field_at_column[i]];
+            t[i] = rng.range((top(rank) - BAND + 1) as i64, top(rank) as i64 + 1) as i32;
+        } else if rng.chance(0.25) {
+            let bad = rng.range(top(FIELDS - 1) as i64 + 1, 1000) as i32;
+            t[rng.below(FIELDS)] = bad;
+            error_rate += bad;
+        }
+        nearby.push(t);
+    }
+    rng.shuffle(&mut nearby);
+
+    let departures: i64 = (0..FIELDS)
+        .filter(|c| field_at_column[*c] < DEPARTURES)
+        .map(|c| mine[c] as i64)
+        .product();
+    let join = |t: &Vec<i32>| t.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",");
+    let input = format!(
+        "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        rules.join("\n"),
+        join(&mine),
+        nearby.iter().map(join).collect::<Vec<String>>().join("\n")
+    );
+    Generated::new(input)
+        .with_answer(1, error_rate)
+        .with_answer(2, departures)
+}
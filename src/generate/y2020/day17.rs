@@ -0,0 +1,14 @@
+use super::{Generated, Rng};
+
+// Only the starting slice is random: the answers come from simulation, so no answer is planted.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let input = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    Generated::new(input)
+}
@@ -0,0 +1,100 @@
+use super::{Generated, Rng};
+
+// A flat sequence of terms joined by `+` or `*`; parentheses nest another one.
+struct Expr {
+    terms: Vec<Term>,
+    ops: Vec<char>,
+}
+
+enum Term {
+    Digit(u64),
+    Paren(Expr),
+}
+
+impl Expr {
+    fn random(depth: usize, rng: &mut Rng) -> Expr {
+        let n = rng.range(2, 6) as usize;
+        let terms = (0..n)
+            .map(|_| {
+                if depth < 3 && rng.chance(0.25) {
+                    Term::Paren(Expr::random(depth + 1, rng))
+                } else {
+                    Term::Digit(rng.range(1, 10) as u64)
+                }
+            })
+            .collect();
+        let ops = (1..n).map(|_| *rng.pick(&['+', '*'])).collect();
+        Expr { terms, ops }
+    }
+
+    fn render(&self) -> String {
+        let mut s = self.terms[0].render();
+        for (op, term) in self.ops.iter().zip(self.terms[1..].iter()) {
+            s += &format!(" {} {}", op, term.render());
+        }
+        s
+    }
+
+    // Part 1: strictly left to right.
+    fn same_precedence(&self) -> Option<u64> {
+        let mut acc = self.terms[0].value(Expr::same_precedence)?;
+        for (op, term) in self.ops.iter().zip(self.terms[1..].iter()) {
+            let v = term.value(Expr::same_precedence)?;
+            acc = if *op == '+' { acc.checked_add(v)? } else { acc.checked_mul(v)? };
+        }
+        Some(acc)
+    }
+
+    // Part 2: addition binds tighter, so multiply together the sums between each `*`.
+    fn addition_first(&self) -> Option<u64> {
+        let mut product = 1u64;
+        let mut sum = self.terms[0].value(Expr::addition_first)?;
+        for (op, term) in self.ops.iter().zip(self.terms[1..].iter()) {
+            let v = term.value(Expr::addition_first)?;
+            if *op == '+' {
+                sum = sum.checked_add(v)?;
+            } else {
+                product = product.checked_mul(sum)?;
+                sum = v;
+            }
+        }
+        product.checked_mul(sum)
+    }
+}
+
+impl Term {
+    fn render(&self) -> String {
+        match self {
+            Term::Digit(d) => d.to_string(),
+            Term::Paren(e) => format!("({})", e.render()),
+        }
+    }
+
+    fn value(&self, eval: fn(&Expr) -> Option<u64>) -> Option<u64> {
+        match self {
+            Term::Digit(d) => Some(*d),
+            Term::Paren(e) => eval(e),
+        }
+    }
+}
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut lines = Vec::with_capacity(size);
+    let (mut part1, mut part2) = (0u64, 0u64);
+    while lines.len() < size {
+        let expr = Expr::random(0, rng);
+        // Keep the totals well clear of overflow, too.
+        let limit = u64::MAX / (4 * size as u64);
+        match (expr.same_precedence(), expr.addition_first()) {
+            (Some(a), Some(b)) if a < limit && b < limit => {
+                part1 += a;
+                part2 += b;
+                lines.push(expr.render());
+            }
+            _ => continue,
+        }
+    }
+    Generated::new(lines.join("\n"))
+        .with_answer(1, part1)
+        .with_answer(2, part2)
+}
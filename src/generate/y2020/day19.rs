@@ -0,0 +1,125 @@
+use super::{Generated, Rng};
+use std::collections::HashSet;
+
+// Ids with a fixed meaning in the puzzle (and in part 2's replacement rules).
+const RESERVED: [usize; 7] = [0, 1, 2, 8, 11, 31, 42];
+const A: usize = 1;
+const B: usize = 2;
+
+// Every rule matches strings of a single length, so messages split into fixed-size chunks.
+struct Grammar {
+    rules: Vec<(usize, String)>,
+    next_id: usize,
+}
+
+impl Grammar {
+    fn add(&mut self, id: Option<usize>, body: String) -> usize {
+        let id = id.unwrap_or_else(|| {
+            while RESERVED.contains(&self.next_id) {
+                self.next_id += 1;
+            }
+            self.next_id += 1;
+            self.next_id - 1
+        });
+        self.rules.push((id, body));
+        id
+    }
+
+    // A random rule matching some strings of length `len`, and the set of strings it matches.
+    fn build(&mut self, len: usize, rng: &mut Rng) -> (usize, HashSet<String>) {
+        if len == 1 {
+            return match rng.below(3) {
+                0 => (A, ["a".to_string()].iter().cloned().collect()),
+                1 => (B, ["b".to_string()].iter().cloned().collect()),
+                _ => {
+                    let id = self.add(None, format!("{} | {}", A, B));
+                    (id, ["a".to_string(), "b".to_string()].iter().cloned().collect())
+                }
+            };
+        }
+        let mut alternatives = Vec::new();
+        let mut language = HashSet::new();
+        for _ in 0..rng.range(1, 3) {
+            let split = rng.range(1, len as i64) as usize;
+            let (left, left_lang) = self.build(split, rng);
+            let (right, right_lang) = self.build(len - split, rng);
+            alternatives.push(format!("{} {}", left, right));
+            for l in left_lang.iter() {
+                for r in right_lang.iter() {
+                    language.insert(format!("{}{}", l, r));
+                }
+            }
+        }
+        (self.add(None, alternatives.join(" | ")), language)
+    }
+
+    // A rule for `first` followed by any of the strings `rest` matches.
+    fn prefixed(&mut self, id: usize, first: usize, len: usize, rng: &mut Rng) -> HashSet<String> {
+        let (rest, rest_lang) = self.build(len - 1, rng);
+        self.add(Some(id), format!("{} {}", first, rest));
+        let c = if first == A { 'a' } else { 'b' };
+        rest_lang.iter().map(|s| format!("{}{}", c, s)).collect()
+    }
+}
+
+// The number of leading 42 chunks and trailing 31 chunks in `message`, if that's all it is.
+fn split(message: &str, len: usize, forty_two: &[String], thirty_one: &[String]) -> Option<(usize, usize)> {
+    if !message.len().is_multiple_of(len) {
+        return None;
+    }
+    let chunks: Vec<&str> = (0..message.len() / len).map(|i| &message[i * len..(i + 1) * len]).collect();
+    let a = chunks.iter().take_while(|c| forty_two.iter().any(|s| s == *c)).count();
+    let b = chunks[a..].iter().take_while(|c| thirty_one.iter().any(|s| s == *c)).count();
+    if a + b == chunks.len() {
+        Some((a, b))
+    } else {
+        None
+    }
+}
+
+// Rule 42 only matches chunks starting with `a` and rule 31 only those starting with `b`, so a
+// message is a sequence of 42 and 31 chunks in at most one way.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let len = rng.range(4, 8) as usize;
+    let mut grammar = Grammar { rules: Vec::new(), next_id: 3 };
+    grammar.add(Some(0), "8 11".to_string());
+    grammar.add(Some(8), "42".to_string());
+    grammar.add(Some(11), "42 31".to_string());
+    grammar.add(Some(A), "\"a\"".to_string());
+    grammar.add(Some(B), "\"b\"".to_string());
+    let forty_two: Vec<String> = grammar.prefixed(42, A, len, rng).into_iter().collect();
+    let thirty_one: Vec<String> = grammar.prefixed(31, B, len, rng).into_iter().collect();
+
+    let random_chunk = |rng: &mut Rng| -> String {
+        (0..len).map(|_| if rng.chance(0.5) { 'a' } else { 'b' }).collect()
+    };
+    let mut messages = Vec::with_capacity(size);
+    let (mut part1, mut part2) = (0, 0);
+    for _ in 0..size {
+        // a chunks from 42 then b from 31; part 2 matches whenever a > b >= 1.
+        let b = rng.range(1, 5) as usize;
+        let a = rng.range(1, 7) as usize;
+        let mut chunks: Vec<String> = Vec::with_capacity(a + b);
+        chunks.extend((0..a).map(|_| rng.pick(&forty_two).clone()));
+        chunks.extend((0..b).map(|_| rng.pick(&thirty_one).clone()));
+        if rng.chance(0.2) {
+            let i = rng.below(chunks.len());
+            chunks[i] = random_chunk(rng);
+        }
+        let mut message = chunks.concat();
+        if rng.chance(0.05) {
+            message.push('a');
+        }
+        // Corrupting a chunk might still leave a valid message, so classify what we ended up with.
+        if let Some((a, b)) = split(&message, len, &forty_two, &thirty_one) {
+            part1 += (a == 2 && b == 1) as i32;
+            part2 += (a > b && b >= 1) as i32;
+        }
+        messages.push(message);
+    }
+    rng.shuffle(&mut grammar.rules);
+    let rules: Vec<String> = grammar.rules.iter().map(|(id, body)| format!("{}: {}", id, body)).collect();
+    Generated::new(format!("{}\n\n{}", rules.join("\n"), messages.join("\n")))
+        .with_answer(1, part1)
+        .with_answer(2, part2)
+}
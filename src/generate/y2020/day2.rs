@@ -0,0 +1,26 @@
+use super::{Generated, Rng};
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut lines = Vec::with_capacity(size);
+    let (mut part1, mut part2) = (0, 0);
+    for _ in 0..size {
+        let password = rng.word(5, 20);
+        let chars: Vec<char> = password.chars().collect();
+        // Pick the policy letter from the password half the time, so that policies pass often enough.
+        let req = if rng.chance(0.5) {
+            *rng.pick(&chars)
+        } else {
+            (b'a' + rng.below(26) as u8) as char
+        };
+        // Positions double as counts for part 1, so keep them within the password.
+        let lo = rng.range(1, chars.len() as i64) as usize;
+        let hi = rng.range(lo as i64 + 1, chars.len() as i64 + 1) as usize;
+        let count = chars.iter().filter(|c| **c == req).count();
+        part1 += (lo <= count && count <= hi) as i32;
+        part2 += ((chars[lo - 1] == req) != (chars[hi - 1] == req)) as i32;
+        lines.push(format!("{}-{} {}: {}", lo, hi, req, password));
+    }
+    Generated::new(lines.join("\n"))
+        .with_answer(1, part1)
+        .with_answer(2, part2)
+}
@@ -0,0 +1,146 @@
+use super::{Generated, Rng};
+use std::collections::HashSet;
+
+// The real tiles are 10x10, i.e. 8x8 inside their borders.
+const INNER: usize = 8;
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+type Cells = Vec<Vec<bool>>;
+
+// Orientation `o` (0..8) of a square grid: `o % 4` quarter turns, flipped first if `o >= 4`.
+fn orient(grid: &Cells, o: usize) -> Cells {
+    let n = grid.len();
+    let mut g: Cells = if o >= 4 { grid.iter().map(|r| r.iter().rev().cloned().collect()).collect() } else { grid.clone() };
+    for _ in 0..o % 4 {
+        g = (0..n).map(|i| (0..n).map(|j| g[n - j - 1][i]).collect()).collect();
+    }
+    g
+}
+
+// The monster's cells in orientation `o`, as offsets from the top-left of its bounding box.
+fn monster(o: usize) -> Vec<(usize, usize)> {
+    let cells: Vec<(i64, i64)> = MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.char_indices().filter(|(_, c)| *c == '#').map(move |(j, _)| (i as i64, j as i64)))
+        .map(|(i, j)| if o >= 4 { (i, -j) } else { (i, j) })
+        .map(|(i, j)| (0..o % 4).fold((i, j), |(i, j), _| (j, -i)))
+        .collect();
+    let (min_i, min_j) = (cells.iter().map(|c| c.0).min().unwrap(), cells.iter().map(|c| c.1).min().unwrap());
+    cells.iter().map(|(i, j)| ((i - min_i) as usize, (j - min_j) as usize)).collect()
+}
+
+// Every monster in orientation `o`, by the cells it covers.
+fn find_monsters(image: &Cells, o: usize) -> Vec<Vec<(usize, usize)>> {
+    let shape = monster(o);
+    let (h, w) = (shape.iter().map(|c| c.0).max().unwrap(), shape.iter().map(|c| c.1).max().unwrap());
+    let n = image.len();
+    let mut found = Vec::new();
+    for i in 0..n.saturating_sub(h) {
+        for j in 0..n.saturating_sub(w) {
+            if shape.iter().all(|(di, dj)| image[i + di][j + dj]) {
+                found.push(shape.iter().map(|(di, dj)| (i + di, j + dj)).collect());
+            }
+        }
+    }
+    found
+}
+
+// An image with monsters in one orientation only, and the number of `#` not part of a monster.
+fn image(n: usize, rng: &mut Rng) -> (Cells, usize) {
+    loop {
+        let mut image: Cells = (0..n).map(|_| (0..n).map(|_| rng.chance(0.3)).collect()).collect();
+        let o = rng.below(8);
+        let shape = monster(o);
+        let (h, w) = (shape.iter().map(|c| c.0).max().unwrap(), shape.iter().map(|c| c.1).max().unwrap());
+        for _ in 0..(n * n / 400).max(1) {
+            let (i, j) = (rng.below(n - h), rng.below(n - w));
+            for (di, dj) in shape.iter() {
+                image[i + di][j + dj] = true;
+            }
+        }
+        if (0..8).any(|other| other != o && !find_monsters(&image, other).is_empty()) {
+            continue;
+        }
+        let covered: HashSet<(usize, usize)> = find_monsters(&image, o).into_iter().flatten().collect();
+        let roughness = image.iter().flatten().filter(|c| **c).count() - covered.len();
+        return (image, roughness);
+    }
+}
+
+// Tiles are cut from the image with random borders, such that neighbours share an edge and no
+// two edges elsewhere match (in either direction), then each is rotated or flipped at random.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    // The image must be big enough for at least one monster.
+    let n = size.max(3);
+    // Grow the tiles for large inputs, so there are enough distinct edges to go around.
+    let mut inner = INNER;
+    while 1 << inner < n * (n + 1) {
+        inner += 1;
+    }
+    let tile = inner + 2;
+    let (image, roughness) = image(n * inner, rng);
+
+    // Corners are shared by up to four tiles, so fix them first.
+    let corners: Cells = (0..=n).map(|_| (0..=n).map(|_| rng.chance(0.5)).collect()).collect();
+    let mut used: HashSet<Vec<bool>> = HashSet::new();
+    let mut edge = |a: bool, b: bool, rng: &mut Rng| -> Vec<bool> {
+        loop {
+            let mut e = vec![a];
+            e.extend((0..inner).map(|_| rng.chance(0.5)));
+            e.push(b);
+            let rev: Vec<bool> = e.iter().rev().cloned().collect();
+            if e != rev && !used.contains(&e) && !used.contains(&rev) {
+                used.insert(e.clone());
+                return e;
+            }
+        }
+    };
+    // horizontal[y][x] runs left to right along the top of tile (y, x); vertical[y][x] runs top
+    // to bottom along its left side.
+    let horizontal: Vec<Vec<Vec<bool>>> = (0..=n)
+        .map(|y| (0..n).map(|x| edge(corners[y][x], corners[y][x + 1], rng)).collect())
+        .collect();
+    let vertical: Vec<Vec<Vec<bool>>> = (0..n)
+        .map(|y| (0..=n).map(|x| edge(corners[y][x], corners[y + 1][x], rng)).collect())
+        .collect();
+
+    let mut ids: HashSet<i64> = HashSet::new();
+    while ids.len() < n * n {
+        ids.insert(rng.range(1000, 10000));
+    }
+    let mut ids: Vec<i64> = ids.into_iter().collect();
+    rng.shuffle(&mut ids);
+
+    let mut tiles = Vec::with_capacity(n * n);
+    for y in 0..n {
+        for x in 0..n {
+            let mut cells: Cells = vec![vec![false; tile]; tile];
+            for k in 0..tile {
+                cells[0][k] = horizontal[y][x][k];
+                cells[tile - 1][k] = horizontal[y + 1][x][k];
+                cells[k][0] = vertical[y][x][k];
+                cells[k][tile - 1] = vertical[y][x + 1][k];
+            }
+            for i in 0..inner {
+                for j in 0..inner {
+                    cells[i + 1][j + 1] = image[y * inner + i][x * inner + j];
+                }
+            }
+            let rows: Vec<String> = orient(&cells, rng.below(8))
+                .iter()
+                .map(|r| r.iter().map(|c| if *c { '#' } else { '.' }).collect())
+                .collect();
+            tiles.push(format!("Tile {}:\n{}", ids[y * n + x], rows.join("\n")));
+        }
+    }
+    rng.shuffle(&mut tiles);
+    let corner_ids = ids[0] * ids[n - 1] * ids[n * (n - 1)] * ids[n * n - 1];
+    Generated::new(tiles.join("\n\n"))
+        .with_answer(1, corner_ids)
+        .with_answer(2, roughness)
+}
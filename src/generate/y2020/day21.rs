@@ -0,0 +1,82 @@
+use super::{Generated, Rng};
+use std::collections::{BTreeMap, HashSet};
+
+fn distinct_words(count: usize, rng: &mut Rng, seen: &mut HashSet<String>) -> Vec<String> {
+    let mut words = Vec::with_capacity(count);
+    while words.len() < count {
+        let w = rng.word(4, 8);
+        if seen.insert(w.clone()) {
+            words.push(w);
+        }
+    }
+    words
+}
+
+// Each allergen is in exactly one ingredient. Foods list a few allergens along with their
+// ingredients, and the foods listing any one allergen have only its ingredient in common, so
+// every allergen is pinned down directly.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut seen = HashSet::new();
+    let allergens = distinct_words(rng.range(3, 9) as usize, rng, &mut seen);
+    // Ingredient i contains allergen i, for i < allergens.len(); the rest are safe.
+    let ingredients = distinct_words(allergens.len() + size * 5, rng, &mut seen);
+    let is_safe = |i: &usize| *i >= allergens.len();
+
+    // The ingredients of a food listing `listed`, leaving out any in `avoid`.
+    let food = |listed: &[usize], avoid: &HashSet<usize>, rng: &mut Rng| -> Vec<usize> {
+        let mut food: Vec<usize> = (0..rng.range(5, 20))
+            .map(|_| rng.range(allergens.len() as i64, ingredients.len() as i64) as usize)
+            .filter(|i| !avoid.contains(i))
+            .chain(listed.iter().cloned())
+            .collect();
+        food.sort_unstable();
+        food.dedup();
+        food
+    };
+    // (ingredients, listed allergens)
+    let mut foods: Vec<(Vec<usize>, Vec<usize>)> = (0..size)
+        .map(|_| {
+            let mut listed: Vec<usize> = (0..allergens.len()).filter(|_| rng.chance(0.3)).collect();
+            if listed.is_empty() {
+                listed.push(rng.below(allergens.len()));
+            }
+            (food(&listed, &HashSet::new(), rng), listed)
+        })
+        .collect();
+    for a in 0..allergens.len() {
+        loop {
+            // None if no food lists the allergen yet.
+            let common: Option<HashSet<usize>> = foods
+                .iter()
+                .filter(|(_, listed)| listed.contains(&a))
+                .map(|(food, _)| food.iter().cloned().collect::<HashSet<usize>>())
+                .fold(None, |acc, s| Some(acc.map_or(s.clone(), |acc| &acc & &s)));
+            if common.as_ref().is_some_and(|c| c.len() == 1) {
+                break;
+            }
+            let avoid = common.unwrap_or_default().into_iter().filter(|i| *i != a).collect();
+            foods.push((food(&[a], &avoid, rng), vec![a]));
+        }
+    }
+    rng.shuffle(&mut foods);
+
+    let lines: Vec<String> = foods
+        .iter()
+        .map(|(food, listed)| {
+            let mut names: Vec<&str> = food.iter().map(|i| ingredients[*i].as_str()).collect();
+            rng.shuffle(&mut names);
+            let listed: Vec<&str> = listed.iter().map(|a| allergens[*a].as_str()).collect();
+            format!("{} (contains {})", names.join(" "), listed.join(", "))
+        })
+        .collect();
+    let safe_count = foods.iter().map(|(food, _)| food.iter().filter(|i| is_safe(i)).count()).sum::<usize>();
+    let by_allergen: BTreeMap<&str, &str> = allergens
+        .iter()
+        .zip(ingredients.iter())
+        .map(|(a, i)| (a.as_str(), i.as_str()))
+        .collect();
+    let canonical = by_allergen.values().cloned().collect::<Vec<&str>>().join(",");
+    Generated::new(lines.join("\n"))
+        .with_answer(1, safe_count as i32)
+        .with_answer(2, canonical)
+}
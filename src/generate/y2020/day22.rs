@@ -0,0 +1,29 @@
+use super::{Generated, Rng};
+use std::collections::VecDeque;
+
+// Regular Combat could in principle go on forever, so give up on planting an answer eventually.
+const MAX_ROUNDS: usize = 100_000;
+
+// Recursive Combat is left to the solver; part 1 is cheap to play out here.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut cards: Vec<i32> = (1..=2 * size as i32).collect();
+    rng.shuffle(&mut cards);
+    let (one, two) = cards.split_at(size);
+    let input = format!(
+        "Player 1:\n{}\n\nPlayer 2:\n{}",
+        one.iter().map(|c| c.to_string()).collect::<Vec<String>>().join("\n"),
+        two.iter().map(|c| c.to_string()).collect::<Vec<String>>().join("\n")
+    );
+    let mut decks: [VecDeque<i32>; 2] = [one.iter().cloned().collect(), two.iter().cloned().collect()];
+    for _ in 0..MAX_ROUNDS {
+        if let Some(winner) = decks.iter().find(|d| d.len() == 2 * size) {
+            let score: i32 = winner.iter().rev().zip(1..).map(|(c, i)| c * i).sum();
+            return Generated::new(input).with_answer(1, score);
+        }
+        let (a, b) = (decks[0].pop_front().unwrap(), decks[1].pop_front().unwrap());
+        let w = if a > b { 0 } else { 1 };
+        decks[w].push_back(a.max(b));
+        decks[w].push_back(a.min(b));
+    }
+    Generated::new(input)
+}
@@ -0,0 +1,32 @@
+use super::{Generated, Rng};
+
+const MOVES: usize = 100;
+
+// Cup labels are single digits, so there are at most nine cups. Part 2's million cups are left to
+// the solver; part 1 is cheap to play out here.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let n = size.clamp(5, 9) as u32;
+    let mut cups: Vec<u32> = (1..=n).collect();
+    rng.shuffle(&mut cups);
+    let input: String = cups.iter().map(|c| c.to_string()).collect();
+    // Keep the current cup at the front of the circle.
+    let mut circle = cups.clone();
+    for _ in 0..MOVES {
+        let current = circle[0];
+        let picked: Vec<u32> = circle.drain(1..4).collect();
+        let mut dest = current;
+        loop {
+            dest = if dest == 1 { n } else { dest - 1 };
+            if !picked.contains(&dest) {
+                break;
+            }
+        }
+        let at = circle.iter().position(|c| *c == dest).unwrap() + 1;
+        circle.splice(at..at, picked);
+        circle.rotate_left(1);
+    }
+    let one = circle.iter().position(|c| *c == 1).unwrap();
+    circle.rotate_left(one);
+    let labels: String = circle[1..].iter().map(|c| c.to_string()).collect();
+    Generated::new(input).with_answer(1, labels)
+}
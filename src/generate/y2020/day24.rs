@@ -0,0 +1,32 @@
+use super::{Generated, Rng};
+use std::collections::HashSet;
+
+// Axial coordinates for each direction.
+const DIRECTIONS: [(&str, (i32, i32)); 6] = [
+    ("e", (1, 0)),
+    ("w", (-1, 0)),
+    ("ne", (1, -1)),
+    ("nw", (0, -1)),
+    ("se", (0, 1)),
+    ("sw", (-1, 1)),
+];
+
+// The living art exhibit is left to the solver; part 1 is just counting flips.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut black: HashSet<(i32, i32)> = HashSet::new();
+    let mut lines = Vec::with_capacity(size);
+    for _ in 0..size {
+        let mut line = String::new();
+        let mut tile = (0, 0);
+        for _ in 0..rng.range(10, 25) {
+            let (name, (dq, dr)) = rng.pick(&DIRECTIONS);
+            line += name;
+            tile = (tile.0 + dq, tile.1 + dr);
+        }
+        if !black.insert(tile) {
+            black.remove(&tile);
+        }
+        lines.push(line);
+    }
+    Generated::new(lines.join("\n")).with_answer(1, black.len())
+}
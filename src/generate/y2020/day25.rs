@@ -0,0 +1,27 @@
+use super::{Generated, Rng};
+
+const MOD: i64 = 20201227;
+const SUBJECT: i64 = 7;
+
+fn pow_mod(base: i64, mut exp: i64) -> i64 {
+    let (mut result, mut base) = (1, base % MOD);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % MOD;
+        }
+        base = base * base % MOD;
+        exp >>= 1;
+    }
+    result
+}
+
+// `size` bounds the loop sizes, which is what the solver's brute force search scales with.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let max_loop = (size as i64).clamp(2, MOD - 1);
+    let card_loop = rng.range(1, max_loop);
+    let door_loop = rng.range(1, max_loop);
+    let card_key = pow_mod(SUBJECT, card_loop);
+    let door_key = pow_mod(SUBJECT, door_loop);
+    Generated::new(format!("{}\n{}", card_key, door_key))
+        .with_answer(1, pow_mod(door_key, card_loop))
+}
@@ -0,0 +1,27 @@
+use super::{Generated, Rng};
+
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let width = 31;
+    let density = 0.1 + 0.3 * rng.chance(0.5) as i32 as f64;
+    let rows: Vec<Vec<bool>> = (0..size.max(2))
+        .map(|_| (0..width).map(|_| rng.chance(density)).collect())
+        .collect();
+    // The starting square is never counted, so only look at rows below it.
+    let trees = |(dj, di): (usize, usize)| -> i64 {
+        (1..)
+            .map(|k| (k * di, k * dj % width))
+            .take_while(|(i, _)| *i < rows.len())
+            .filter(|(i, j)| rows[*i][*j])
+            .count() as i64
+    };
+    let input = rows
+        .iter()
+        .map(|row| row.iter().map(|t| if *t { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n");
+    Generated::new(input)
+        .with_answer(1, trees(SLOPES[1]))
+        .with_answer(2, SLOPES.iter().map(|s| trees(*s)).product::<i64>())
+}
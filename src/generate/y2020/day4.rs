@@ -0,0 +1,87 @@
+use super::{Generated, Rng};
+use std::ops::Range;
+
+const REQUIRED: [&str; 7] = ["byr", "ecl", "eyr", "hcl", "hgt", "iyr", "pid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+// A year just below or just above the valid range.
+fn outside(rng: &mut Rng, below: Range<i64>, above: Range<i64>) -> i64 {
+    let r = if rng.chance(0.5) { below } else { above };
+    rng.range(r.start, r.end)
+}
+
+// A value for `field`, and whether it satisfies the part 2 rules.
+fn value(field: &str, rng: &mut Rng) -> (String, bool) {
+    let valid = rng.chance(0.85);
+    let v = match (field, valid) {
+        ("byr", true) => rng.range(1920, 2003).to_string(),
+        ("byr", false) => outside(rng, 1900..1920, 2003..2030).to_string(),
+        ("iyr", true) => rng.range(2010, 2021).to_string(),
+        ("iyr", false) => outside(rng, 1990..2010, 2021..2030).to_string(),
+        ("eyr", true) => rng.range(2020, 2031).to_string(),
+        ("eyr", false) => outside(rng, 2000..2020, 2031..2040).to_string(),
+        ("hgt", true) if rng.chance(0.5) => format!("{}cm", rng.range(150, 194)),
+        ("hgt", true) => format!("{}in", rng.range(59, 77)),
+        ("hgt", false) => match rng.below(3) {
+            0 => format!("{}cm", rng.range(100, 150)),
+            1 => format!("{}in", rng.range(77, 99)),
+            _ => rng.range(59, 194).to_string(),
+        },
+        ("hcl", true) => format!("#{:06x}", rng.below(1 << 24)),
+        ("hcl", false) => match rng.below(2) {
+            0 => format!("{:06x}", rng.below(1 << 24)),
+            _ => format!("#{}", rng.word(6, 6)),
+        },
+        ("ecl", true) => rng.pick(&EYE_COLORS).to_string(),
+        ("ecl", false) => rng.word(3, 3),
+        ("pid", true) => format!("{:09}", rng.below(1_000_000_000)),
+        ("pid", false) => format!("{:010}", rng.below(1_000_000_000)),
+        ("cid", _) => return (rng.range(100, 350).to_string(), true),
+        _ => unreachable!("no such field `{}`", field),
+    };
+    // A random "invalid" hair colour or eye colour can still happen to be valid.
+    let valid = match field {
+        "hcl" => v.len() == 7 && v.starts_with('#') && v[1..].bytes().all(|b| b.is_ascii_hexdigit()),
+        "ecl" => EYE_COLORS.contains(&v.as_str()),
+        _ => valid,
+    };
+    (v, valid)
+}
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut passports = Vec::with_capacity(size);
+    let (mut present, mut valid) = (0, 0);
+    for _ in 0..size {
+        let mut fields: Vec<&str> = REQUIRED
+            .iter()
+            .cloned()
+            .filter(|_| rng.chance(0.97))
+            .collect();
+        if fields.is_empty() || rng.chance(0.7) {
+            fields.push("cid");
+        }
+        rng.shuffle(&mut fields);
+        let mut all_valid = true;
+        let mut tokens = Vec::with_capacity(fields.len());
+        for field in fields.iter() {
+            let (v, ok) = value(field, rng);
+            all_valid &= ok;
+            tokens.push(format!("{}:{}", field, v));
+        }
+        let has_all = fields.iter().filter(|f| **f != "cid").count() == REQUIRED.len();
+        present += has_all as i32;
+        valid += (has_all && all_valid) as i32;
+        // Fields are separated by spaces or single newlines.
+        let mut passport = String::new();
+        for (i, token) in tokens.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+            passport += token;
+        }
+        passports.push(passport);
+    }
+    Generated::new(passports.join("\n\n"))
+        .with_answer(1, present)
+        .with_answer(2, valid)
+}
@@ -0,0 +1,16 @@
+use super::{Generated, Rng};
+use crate::y2020::day5::id_to_seat;
+
+// The plane is full apart from some seats at the front and back, and exactly one in between.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let count = size.clamp(3, 1000) as i32;
+    let first = rng.range(1, (1024 - count) as i64) as i32;
+    let last = first + count;
+    let missing = rng.range((first + 1) as i64, last as i64) as i32;
+    let mut ids: Vec<i32> = (first..=last).filter(|id| *id != missing).collect();
+    rng.shuffle(&mut ids);
+    let input = ids.iter().map(|id| id_to_seat(*id)).collect::<Vec<String>>().join("\n");
+    Generated::new(input)
+        .with_answer(1, last)
+        .with_answer(2, missing)
+}
@@ -0,0 +1,33 @@
+use super::{Generated, Rng};
+
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut groups = Vec::with_capacity(size);
+    let (mut anyone, mut everyone) = (0, 0);
+    for _ in 0..size {
+        let people = rng.range(1, 6) as usize;
+        // Groups tend to share most of their answers, so draw each person's from a common pool.
+        let pool: Vec<usize> = (0..26).filter(|_| rng.chance(0.4)).collect();
+        let mut counts = [0; 26];
+        let mut lines = Vec::with_capacity(people);
+        for _ in 0..people {
+            let mut answers: Vec<usize> = pool.iter().cloned().filter(|_| rng.chance(0.8)).collect();
+            if answers.is_empty() || rng.chance(0.1) {
+                answers.push(rng.below(26));
+                answers.dedup();
+            }
+            answers.sort_unstable();
+            answers.dedup();
+            rng.shuffle(&mut answers);
+            for a in answers.iter() {
+                counts[*a] += 1;
+            }
+            lines.push(answers.iter().map(|a| (b'a' + *a as u8) as char).collect::<String>());
+        }
+        anyone += counts.iter().filter(|c| **c > 0).count();
+        everyone += counts.iter().filter(|c| **c == people).count();
+        groups.push(lines.join("\n"));
+    }
+    Generated::new(groups.join("\n\n"))
+        .with_answer(1, anyone)
+        .with_answer(2, everyone)
+}
@@ -0,0 +1,64 @@
+use super::{Generated, Rng};
+use std::collections::HashSet;
+
+const TARGET: &str = "shiny gold";
+// Bags below shiny gold branch slowly, so that its contents stay countable in an i64.
+const TAIL: usize = 12;
+
+// Bags are numbered in topological order: a bag only ever contains bags with a higher index.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let n = size.max(TAIL + 2);
+    let target = n - TAIL - 1;
+    let mut names: Vec<String> = Vec::with_capacity(n);
+    let mut seen: HashSet<String> = [TARGET.to_string()].iter().cloned().collect();
+    while names.len() < n {
+        let name = format!("{} {}", rng.word(3, 7), rng.word(3, 7));
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names[target] = TARGET.to_string();
+
+    let contents: Vec<Vec<(i64, usize)>> = (0..n)
+        .map(|i| {
+            let (max_children, max_count) = if i >= target { (2, 5) } else { (4, 6) };
+            let mut children: Vec<usize> = (0..rng.below(max_children + 1))
+                .filter(|_| i + 1 < n)
+                .map(|_| rng.range(i as i64 + 1, n as i64) as usize)
+                .collect();
+            children.sort_unstable();
+            children.dedup();
+            children.iter().map(|c| (rng.range(1, max_count + 1), *c)).collect()
+        })
+        .collect();
+
+    // Part 1: everything that can reach shiny gold, working backwards from it.
+    let mut holds_target = vec![false; n];
+    for i in (0..target).rev() {
+        holds_target[i] = contents[i].iter().any(|(_, c)| *c == target || holds_target[*c]);
+    }
+    // Part 2: the bags inside each bag, working backwards from the leaves.
+    let mut inside = vec![0i64; n];
+    for i in (target..n).rev() {
+        inside[i] = contents[i].iter().map(|(k, c)| k * (1 + inside[*c])).sum();
+    }
+
+    let mut rules: Vec<String> = (0..n)
+        .map(|i| {
+            let children = if contents[i].is_empty() {
+                "no other bags".to_string()
+            } else {
+                contents[i]
+                    .iter()
+                    .map(|(k, c)| format!("{} {} bag{}", k, names[*c], if *k == 1 { "" } else { "s" }))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            };
+            format!("{} bags contain {}.", names[i], children)
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+    Generated::new(rules.join("\n"))
+        .with_answer(1, holds_target.iter().filter(|h| **h).count() as i32)
+        .with_answer(2, inside[target])
+}
@@ -0,0 +1,44 @@
+use super::{Generated, Rng};
+
+// The program runs straight down a path of `acc`, `nop` and forward `jmp` instructions, where
+// every `jmp` skips over `jmp +0` traps. A single `jmp` back onto the path is the corrupted
+// instruction: as a `nop`, execution carries on to the end.
+//
+// Flipping any other instruction that executes either lands on a trap (a `jmp` turned `nop`) or
+// jumps back onto the path (a `nop` turned `jmp`), so it still loops.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let n = size.max(8);
+    let corrupt_at = rng.range(1, n as i64 * 3 / 4) as usize;
+    let mut program: Vec<String> = Vec::with_capacity(n);
+    let mut path: Vec<usize> = Vec::new();
+    let (mut acc_before_loop, mut acc) = (0, 0);
+    while program.len() < n {
+        let i = program.len();
+        if i == corrupt_at {
+            let target = *rng.pick(&path);
+            program.push(format!("jmp -{}", i - target));
+            path.push(i);
+            acc_before_loop = acc;
+            continue;
+        }
+        let skip = rng.range(2, 5) as usize;
+        match rng.below(5) {
+            0 if i + skip <= n && !(i..i + skip).contains(&corrupt_at) => {
+                program.push(format!("jmp +{}", skip));
+                program.extend((1..skip).map(|_| "jmp +0".to_string()));
+            }
+            1 if !path.is_empty() => {
+                program.push(format!("nop -{}", i - rng.pick(&path)));
+            }
+            _ => {
+                let arg = rng.range(-50, 51);
+                program.push(format!("acc {:+}", arg));
+                acc += arg;
+            }
+        }
+        path.push(i);
+    }
+    Generated::new(program.join("\n"))
+        .with_answer(1, acc_before_loop)
+        .with_answer(2, acc)
+}
@@ -0,0 +1,66 @@
+use super::{Generated, Rng};
+use std::collections::HashSet;
+
+const RUN: usize = 25;
+
+fn is_valid(window: &[i64], x: i64) -> bool {
+    let set: HashSet<i64> = window.iter().cloned().collect();
+    window.iter().any(|a| set.contains(&(x - a)))
+}
+
+// Every number is the sum of two of the (smaller) numbers in its window, except for a planted
+// target, which is the sum of a contiguous run of earlier numbers.
+pub fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let n = size.max(RUN + 10);
+    let target_at = rng.range((RUN + 5) as i64, n as i64) as usize;
+    let mut numbers: Vec<i64> = (1..=2 * RUN as i64).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(RUN);
+    while numbers.len() < n {
+        let window = &numbers[numbers.len() - RUN..];
+        if numbers.len() == target_at {
+            let start = rng.below(target_at - 2);
+            let end = rng.range(start as i64 + 2, (start + RUN).min(target_at) as i64 + 1) as usize;
+            let target: i64 = numbers[start..end].iter().sum();
+            if !is_valid(window, target) {
+                numbers.push(target);
+            }
+            continue;
+        }
+        // Summing the smallest few keeps the numbers from growing too quickly, but widen the
+        // choice if their sums are all taken.
+        let mut sorted = window.to_vec();
+        sorted.sort_unstable();
+        for pool in 5..=RUN {
+            let a = rng.below(pool);
+            let b = (a + 1 + rng.below(pool - 1)) % pool;
+            let x = sorted[a] + sorted[b];
+            if !window.contains(&x) {
+                numbers.push(x);
+                break;
+            }
+        }
+    }
+    let target = numbers[target_at];
+    // The solver takes the first contiguous run (of at least two numbers) that adds up to the target.
+    let weakness = (0..target_at)
+        .find_map(|i| {
+            let mut sum = numbers[i];
+            for j in i + 1..n {
+                sum += numbers[j];
+                if sum == target {
+                    let run = &numbers[i..=j];
+                    return Some(run.iter().min().unwrap() + run.iter().max().unwrap());
+                }
+                if sum > target {
+                    break;
+                }
+            }
+            None
+        })
+        .unwrap();
+    let input = numbers.iter().map(|v| v.to_string()).collect::<Vec<String>>().join("\n");
+    Generated::new(input)
+        .with_answer(1, target)
+        .with_answer(2, weakness)
+}
@@ -0,0 +1,249 @@
+//! A dense 2D grid, for the puzzles drawn as a picture of characters.
+//!
+//! Positions are `(row, col)`, counted from the top left.
+
+use crate::error::{ParseError, Source};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Pos = (usize, usize);
+
+/// Row and column steps, for moving around a grid.
+pub type Dir = (isize, isize);
+
+pub const DIRS4: [Dir; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+pub const DIRS8: [Dir; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>, // Row by row.
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+    where
+        F: FnMut(Pos) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse `text` (a slice of `src.input`) one character per cell, with `cell` saying what each
+    /// character means, or None for characters that don't belong. Every row must be the same width.
+    pub fn parse<F>(
+        src: &Source,
+        text: &str,
+        expected: &str,
+        cell: F,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in text.lines() {
+            for (j, c) in line.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(
+                            src.error(&line[j..j + c.len_utf8()], format!("expected {}", expected))
+                        )
+                    }
+                }
+            }
+            let w = line.chars().count();
+            match width {
+                Some(width) if width != w => {
+                    return Err(src.error(line, format!("expected a row of width {}", width)));
+                }
+                _ => width = Some(w),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (i, j): Pos) -> bool {
+        i < self.height && j < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `(row, col)`, as if the grid were tiled endlessly in every direction.
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        let i = row.rem_euclid(self.height as isize) as usize;
+        let j = col.rem_euclid(self.width as isize) as usize;
+        &self[(i, j)]
+    }
+
+    /// One step from `pos` in direction `dir`, if that's still on the grid.
+    pub fn step(&self, (i, j): Pos, (di, dj): Dir) -> Option<Pos> {
+        let pos = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+        if self.contains(pos) {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| (i, j)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() would panic on an empty grid.
+        (0..self.height).map(move |i| &self.cells[i * self.width..(i + 1) * self.width])
+    }
+
+    /// The positions up, down, left and right of `pos`.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS4.iter().filter_map(move |dir| self.step(pos, *dir))
+    }
+
+    /// The positions surrounding `pos`, diagonals included.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS8.iter().filter_map(move |dir| self.step(pos, *dir))
+    }
+
+    /// The positions seen looking from `pos` in direction `dir`, nearest first, up to the edge.
+    pub fn ray(&self, pos: Pos, dir: Dir) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, dir), move |pos| self.step(*pos, dir))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Turned a quarter turn clockwise.
+    pub fn rotate(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        Grid::from_fn(self.height, self.width, |(i, j)| {
+            self[(h - j - 1, i)].clone()
+        })
+    }
+
+    /// Mirrored left to right.
+    pub fn flip(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        Grid::from_fn(self.width, self.height, |(i, j)| {
+            self[(i, w - j - 1)].clone()
+        })
+    }
+
+    /// All 8 ways of rotating and flipping the grid, starting with the grid as it is.
+    pub fn orientations(&self) -> impl Iterator<Item = Grid<T>>
+    where
+        T: Clone,
+    {
+        let rotations = |g: Grid<T>| std::iter::successors(Some(g), |g| Some(g.rotate())).take(4);
+        rotations(self.clone()).chain(rotations(self.flip()))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, width, height))
+    }
+}
+
+/// One row per line, with no separator between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
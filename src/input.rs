@@ -0,0 +1,23 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Matches where cargo-aoc keeps inputs, relative to the crate root.
+pub const INPUT_DIR: &str = "input";
+
+pub fn year_dir(year: u32) -> PathBuf {
+    PathBuf::from(INPUT_DIR).join(year.to_string())
+}
+
+pub fn path(year: u32, day: u32) -> PathBuf {
+    path_in(Path::new(INPUT_DIR), year, day)
+}
+
+/// Where the input for a day lives under some other root than `INPUT_DIR`.
+pub fn path_in(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join(year.to_string()).join(format!("day{}.txt", day))
+}
+
+pub fn read(year: u32, day: u32) -> io::Result<String> {
+    fs::read_to_string(path(year, day))
+}
@@ -0,0 +1,112 @@
+//! Sets of integers, stored as sorted, disjoint, non-adjacent inclusive ranges.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn empty() -> IntervalSet {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    pub fn all() -> IntervalSet {
+        IntervalSet::range(i64::MIN, i64::MAX)
+    }
+
+    /// `lo` to `hi`, both included; empty if `lo > hi`.
+    pub fn range(lo: i64, hi: i64) -> IntervalSet {
+        IntervalSet::from_ranges(vec![(lo, hi)])
+    }
+
+    pub fn single(v: i64) -> IntervalSet {
+        IntervalSet::range(v, v)
+    }
+
+    /// Everything from `lo` up.
+    pub fn at_least(lo: i64) -> IntervalSet {
+        IntervalSet::range(lo, i64::MAX)
+    }
+
+    /// Everything up to `hi`.
+    pub fn at_most(hi: i64) -> IntervalSet {
+        IntervalSet::range(i64::MIN, hi)
+    }
+
+    /// Any inclusive ranges, overlapping or not. Empty ones are ignored.
+    pub fn from_ranges(mut ranges: Vec<(i64, i64)>) -> IntervalSet {
+        ranges.retain(|(lo, hi)| lo <= hi);
+        ranges.sort_unstable();
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(ranges.len());
+        for (lo, hi) in ranges {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[(i64, i64)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, v: i64) -> bool {
+        // The last range starting at or below v is the only one that can hold it.
+        let i = self.ranges.partition_point(|(lo, _)| *lo <= v);
+        i > 0 && v <= self.ranges[i - 1].1
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned().collect())
+    }
+
+    pub fn complement(&self) -> IntervalSet {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        let mut next = Some(i64::MIN);
+        for (lo, hi) in self.ranges.iter() {
+            if let Some(start) = next {
+                if start < *lo {
+                    ranges.push((start, lo - 1));
+                }
+            }
+            next = hi.checked_add(1);
+        }
+        if let Some(start) = next {
+            ranges.push((start, i64::MAX));
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        self.complement().union(&other.complement()).complement()
+    }
+}
+
+/// e.g. `<= 3 or 5 or 7-9 or >= 12`, or `nothing`.
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ranges.is_empty() {
+            return write!(f, "nothing");
+        }
+        for (i, (lo, hi)) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, " or ")?;
+            }
+            match (*lo, *hi) {
+                (i64::MIN, i64::MAX) => write!(f, "anything")?,
+                (i64::MIN, hi) => write!(f, "<= {}", hi)?,
+                (lo, i64::MAX) => write!(f, ">= {}", lo)?,
+                (lo, hi) if lo == hi => write!(f, "{}", lo)?,
+                (lo, hi) => write!(f, "{}-{}", lo, hi)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,31 +3,26 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
+pub mod automaton;
+pub mod bench;
+pub mod error;
+pub mod fetch;
+pub mod generate;
+pub mod matching;
+pub mod grid;
+pub mod interval;
+pub mod registry;
+pub mod remote;
+pub mod submit;
+pub mod differential;
+pub mod input;
+pub mod output;
+pub mod parallel;
+pub mod trace;
+pub mod verify;
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+pub mod y2020;
 
+// aoc_runner only supports one year per crate, so only 2020's `#[aoc]` attributes are wired up to
+// it (for cargo-aoc). Every season, 2020 included, is listed in `registry::YEARS`.
 aoc_lib!{ year = 2020 }
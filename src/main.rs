@@ -1,7 +1,578 @@
-extern crate aoc_2020;
-extern crate aoc_runner_derive;
-extern crate aoc_runner;
+//! Command line entry points for the solutions, for any season in the crate.
+//!
+//! ```text
+//! aoc-2020 run <day> [part] [--variant <name>] [--input <path>] [--format text|json]
+//! aoc-2020 bench [day] [part] [--samples <n>] [--warmup <n>] [--baseline <report.json>] [--format text|json|csv]
+//! aoc-2020 verify [--format text|json]
+//! aoc-2020 run-all [--threads <n>] [--format text|json]
+//! aoc-2020 generate <day> [--size <n>] [--seed <n>] [--format text|json]
+//! aoc-2020 explain <day> [part]
+//! aoc-2020 diff <day> [part] [--input <path>]
+//! aoc-2020 fetch <day>
+//! aoc-2020 submit <day> <part> [--variant <name>] [--input <path>]
+//! ```
+//!
+//! Every command works on the latest season unless given `--year <year>`. Inputs default to
+//! `input/<year>/day<N>.txt`; pass `--input -` to read stdin instead. `run` and `fetch` download
+//! missing inputs into that cache, given a session cookie in `AOC_SESSION` (and optionally another
+//! server in `AOC_BASE_URL`). `submit` solves a part and sends the answer to the same server,
+//! logging every attempt in `input/submissions.txt` so that known-wrong answers are never resent.
+//!
+//! Results go to stdout and diagnostics to stderr. With `--format json`, results are an array of
+//! `output::RunResult` objects (plus any command-specific fields), with answers as JSON numbers or
+//! strings and errors as structured diagnostics. The exit status is 0 on success, 1 if anything
+//! failed (a solver error, a wrong answer, a bench regression or variants that disagree) and 2 for
+//! bad usage.
+//!
+//! Solvers are silent by default. Any command takes `-v` (phase timings and debug events) or `-vv`
+//! (everything), limited to particular days with `--trace <day>` and redirected with
+//! `--trace-file <path>`, e.g. `aoc-2020 -vv --trace 20 run 20 2`.
 
-use aoc_runner_derive::aoc_main;
+use aoc_2020::bench::{self, Config};
+use aoc_2020::differential::{self, Outcome};
+use aoc_2020::fetch::{FetchError, Fetcher};
+use aoc_2020::generate::{self, Rng};
+use aoc_2020::input;
+use aoc_2020::output::RunResult;
+use aoc_2020::parallel::{self, Table};
+use aoc_2020::registry::{self, Solution, Solver};
+use aoc_2020::submit::{Submitter, Verdict};
+use aoc_2020::trace;
+use aoc_2020::verify::{self, Manifest, Status};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::json;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-aoc_main! { lib = aoc_2020 }
+#[derive(Parser)]
+#[command(
+    name = "aoc-2020",
+    about = "Run, check and benchmark the Advent of Code solutions"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Which season to work on. Defaults to the latest one.
+    #[arg(long, global = true)]
+    year: Option<u32>,
+    /// Log what the solvers are doing: once for phases and debug events, twice for everything.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Only log these days. May be repeated; defaults to every day.
+    #[arg(long = "trace", value_name = "DAY", global = true)]
+    trace_days: Vec<u32>,
+    /// Write the log here instead of stderr. Implies -v.
+    #[arg(long, value_name = "PATH", global = true)]
+    trace_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or one part of it.
+    Run {
+        day: u32,
+        /// Both parts if omitted.
+        part: Option<u32>,
+        /// Which implementation to use, e.g. `for_loop`. Defaults to the first one registered.
+        #[arg(long)]
+        variant: Option<String>,
+        /// Puzzle input file, or `-` for stdin.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Time every solver, optionally restricted to a day or part.
+    Bench {
+        day: Option<u32>,
+        part: Option<u32>,
+        /// Puzzle input file, or `-` for stdin. Requires a day.
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        #[arg(long, default_value_t = Config::default().warmup)]
+        warmup: usize,
+        #[arg(long, default_value_t = Config::default().samples)]
+        samples: usize,
+        /// A previous `--format json` report to compare against.
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// How much slower than the baseline counts as a regression, e.g. 0.1 for 10%.
+        #[arg(long, default_value_t = 0.1)]
+        threshold: f64,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Check every solver against the known answers in input/<year>/answers.txt.
+    Verify {
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Print a random input for a day, with any answers known by construction on stderr.
+    Generate {
+        day: u32,
+        /// Defaults to roughly the size of the real input.
+        #[arg(long)]
+        size: Option<usize>,
+        /// Defaults to the current time; the seed used is always reported, so it can be replayed.
+        #[arg(long)]
+        seed: Option<u64>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Run every solver at once across a pool of threads, and summarize what each one cost.
+    RunAll {
+        /// Defaults to the number of CPUs.
+        #[arg(long)]
+        threads: Option<usize>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Describe the solvers registered for a day, its input and its known answers.
+    Explain { day: u32, part: Option<u32> },
+    /// Run every variant of a day on the same input and report whether they agree.
+    Diff {
+        day: u32,
+        /// Every part with more than one variant if omitted.
+        part: Option<u32>,
+        /// Puzzle input file, or `-` for stdin.
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Download the input for a day, unless it's already cached.
+    Fetch { day: u32 },
+    /// Solve one part and submit the answer.
+    Submit {
+        day: u32,
+        part: u32,
+        /// Which implementation to use. Defaults to the first one registered.
+        #[arg(long)]
+        variant: Option<String>,
+        /// Puzzle input file, or `-` for stdin.
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    /// Only supported by `bench`.
+    Csv,
+}
+
+fn read_input(year: u32, day: u32, path: &Option<PathBuf>) -> Result<String, String> {
+    match path {
+        Some(p) if p.as_os_str() == "-" => {
+            let mut s = String::new();
+            io::stdin()
+                .read_to_string(&mut s)
+                .map_err(|e| format!("could not read stdin: {}", e))?;
+            Ok(s)
+        }
+        Some(p) => {
+            fs::read_to_string(p).map_err(|e| format!("could not read {}: {}", p.display(), e))
+        }
+        None => Fetcher::from_env()
+            .get(year, day)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+// A run result, with some command-specific fields alongside.
+fn with_fields(result: RunResult, fields: serde_json::Value) -> serde_json::Value {
+    let mut value = serde_json::to_value(result).unwrap();
+    if let (Some(object), serde_json::Value::Object(fields)) = (value.as_object_mut(), fields) {
+        object.extend(fields);
+    }
+    value
+}
+
+fn parts(part: Option<u32>) -> Vec<u32> {
+    part.map_or(vec![1, 2], |p| vec![p])
+}
+
+fn unsupported(format: Format, command: &str) -> Result<(), String> {
+    if format == Format::Csv {
+        return Err(format!("`{}` does not support --format csv", command));
+    }
+    Ok(())
+}
+
+fn run(
+    year: u32,
+    day: u32,
+    part: Option<u32>,
+    variant: Option<String>,
+    path: Option<PathBuf>,
+    format: Format,
+) -> Result<bool, String> {
+    unsupported(format, "run")?;
+    let solutions = parts(part)
+        .into_iter()
+        .map(|p| {
+            registry::find(year, day, p, variant.as_deref()).ok_or_else(|| {
+                let names: Vec<&str> = registry::variants(year, day, p)
+                    .filter_map(|s| s.variant)
+                    .collect();
+                match registry::variants(year, day, p).next() {
+                    None => format!("no solver for day {} part {}", day, p),
+                    Some(_) if names.is_empty() => {
+                        format!("day {} part {} has no variants", day, p)
+                    }
+                    Some(_) => format!("no such variant; try one of: {}", names.join(", ")),
+                }
+            })
+        })
+        // Day 25 only has a part 1, so don't complain about part 2 unless it was asked for.
+        .filter(|s| {
+            part.is_some() || s.is_ok() || registry::variants(year, day, 1).next().is_none()
+        })
+        .collect::<Result<Vec<&Solution>, String>>()?;
+    let input = read_input(year, day, &path)?;
+    let mut ok = true;
+    let mut results = Vec::new();
+    for s in solutions {
+        let start = Instant::now();
+        let outcome = Outcome::of(s, &input);
+        let elapsed = start.elapsed();
+        ok &= matches!(outcome, Outcome::Solved(_));
+        match format {
+            Format::Json => results.push(RunResult::new(s, Some(&outcome), elapsed)),
+            _ => match &outcome {
+                Outcome::Solved(answer) => println!("{}: {} ({:?})", s.name(), answer, elapsed),
+                Outcome::Rejected(e) => eprintln!("{}: error\n{}", s.name(), e),
+                Outcome::Panicked(_) => eprintln!("{}: {}", s.name(), outcome),
+            },
+        }
+    }
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&results).unwrap());
+    }
+    Ok(ok)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn bench(
+    year: u32,
+    day: Option<u32>,
+    part: Option<u32>,
+    path: Option<PathBuf>,
+    warmup: usize,
+    samples: usize,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    format: Format,
+) -> Result<bool, String> {
+    let solutions = registry::solutions(year)
+        .iter()
+        .filter(|s| day.is_none_or(|d| s.day == d) && part.is_none_or(|p| s.part == p));
+    let input = match (day, &path) {
+        (Some(d), Some(_)) => Some(read_input(year, d, &path)?),
+        _ => None,
+    };
+    let config = Config {
+        warmup,
+        samples,
+        ..Config::default()
+    };
+    let (report, skipped) = bench::run(
+        solutions,
+        |y, d| input.clone().or_else(|| input::read(y, d).ok()),
+        &config,
+    );
+    for (s, reason) in skipped.iter() {
+        eprintln!(
+            "skipped {}: {}",
+            s.name(),
+            reason.lines().next().unwrap_or("")
+        );
+    }
+    match format {
+        Format::Text => print!("{}", report),
+        Format::Json => println!("{}", report.to_json()),
+        Format::Csv => print!("{}", report.to_csv()),
+    }
+    let mut ok = skipped.is_empty();
+    if let Some(baseline) = baseline {
+        let text = fs::read_to_string(&baseline)
+            .map_err(|e| format!("could not read {}: {}", baseline.display(), e))?;
+        let baseline =
+            bench::Report::from_json(&text).map_err(|e| format!("bad baseline report: {}", e))?;
+        for c in report.compare(&baseline, threshold) {
+            eprintln!("{}", c);
+            ok &= !c.regressed;
+        }
+    }
+    Ok(ok)
+}
+
+fn verify(year: u32, format: Format) -> Result<bool, String> {
+    unsupported(format, "verify")?;
+    let manifest = Manifest::load(year)?;
+    let entries = verify::verify(&manifest);
+    match format {
+        Format::Json => {
+            let results: Vec<_> = entries
+                .iter()
+                .map(|e| {
+                    with_fields(
+                        RunResult::new(e.solution, e.outcome.as_ref(), e.elapsed),
+                        json!({ "status": e.status(), "expected": e.expected }),
+                    )
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&results).unwrap());
+        }
+        _ => {
+            for e in entries.iter() {
+                println!("{}", e);
+            }
+            println!("{}", verify::summarize(&entries));
+        }
+    }
+    Ok(entries.iter().all(|e| e.status() != Status::Fail))
+}
+
+fn run_all(year: u32, threads: Option<usize>, format: Format) -> Result<bool, String> {
+    unsupported(format, "run-all")?;
+    let threads = threads.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let manifest = Manifest::load(year).ok();
+    let start = Instant::now();
+    let timings = parallel::run_all(
+        registry::solutions(year),
+        |y, d| input::read(y, d).ok(),
+        threads,
+    );
+    let table = Table::new(&timings, manifest.as_ref(), start.elapsed());
+    match format {
+        Format::Json => {
+            let results: Vec<_> = table
+                .timings
+                .iter()
+                .map(|t| {
+                    with_fields(
+                        RunResult::new(t.solution, t.outcome.as_ref(), t.wall),
+                        json!({
+                            "status": table.status(t),
+                            "cpu_ns": t.cpu.as_nanos() as u64,
+                            "heavy": table.is_heavy(t),
+                        }),
+                    )
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&results).unwrap());
+        }
+        _ => println!("{}", table),
+    }
+    Ok(table.ok())
+}
+
+fn generate(
+    year: u32,
+    day: u32,
+    size: Option<usize>,
+    seed: Option<u64>,
+    format: Format,
+) -> Result<bool, String> {
+    unsupported(format, "generate")?;
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_nanos() as u64
+    });
+    let size = size.unwrap_or_else(|| generate::default_size(year, day));
+    let generated = generate::generate(year, day, size, &mut Rng::new(seed))
+        .ok_or_else(|| format!("no generator for {} day {}", year, day))?;
+    match format {
+        Format::Json => {
+            let answers: serde_json::Map<String, serde_json::Value> = generated
+                .answers
+                .iter()
+                .map(|(part, answer)| (part.to_string(), json!(answer)))
+                .collect();
+            let result = json!({
+                "year": year,
+                "day": day,
+                "seed": seed,
+                "size": size,
+                "input": generated.input,
+                "answers": answers,
+            });
+            println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        }
+        _ => {
+            println!("{}", generated.input);
+            eprintln!("{} day {}, size {}, seed {}", year, day, size, seed);
+            for (part, answer) in generated.answers.iter() {
+                eprintln!("part {}: {}", part, answer);
+            }
+        }
+    }
+    Ok(true)
+}
+
+fn explain(year: u32, day: u32, part: Option<u32>) -> Result<bool, String> {
+    let manifest = Manifest::load(year).ok();
+    println!("{} Day {}", year, day);
+    let path = input::path(year, day);
+    match fs::read_to_string(&path) {
+        Ok(text) => println!("input: {} ({} lines)", path.display(), text.lines().count()),
+        Err(_) => println!("input: {} (missing)", path.display()),
+    }
+    for p in parts(part) {
+        let solutions: Vec<&Solution> = registry::variants(year, day, p).collect();
+        if solutions.is_empty() {
+            continue;
+        }
+        println!("Part {}", p);
+        for (i, s) in solutions.iter().enumerate() {
+            let default = if i == 0 { " (default)" } else { "" };
+            println!("\tsolver: {}{}", s.name(), default);
+        }
+        match manifest.as_ref().and_then(|m| m.expected(day, p)) {
+            Some(answer) => println!("\tknown answer: {}", answer),
+            None => println!("\tknown answer: none recorded"),
+        }
+    }
+    Ok(true)
+}
+
+fn diff(year: u32, day: u32, part: Option<u32>, path: Option<PathBuf>) -> Result<bool, String> {
+    for p in parts(part) {
+        if part.is_some() && registry::variants(year, day, p).next().is_none() {
+            return Err(format!("no solver for day {} part {}", day, p));
+        }
+    }
+    let input = read_input(year, day, &path)?;
+    let reports = match part {
+        Some(p) => vec![differential::check(year, day, p, &input)],
+        None => differential::check_day(year, day, &input),
+    };
+    if reports.is_empty() {
+        eprintln!("day {} has no part with more than one variant", day);
+    }
+    for report in reports.iter() {
+        print!("{}", report);
+    }
+    Ok(reports.iter().all(|r| r.agrees()))
+}
+
+fn fetch(year: u32, day: u32) -> Result<bool, String> {
+    let fetcher = Fetcher::from_env();
+    match fetcher.fetch(year, day) {
+        Ok(_) => {
+            println!("{}", fetcher.path(year, day).display());
+            Ok(true)
+        }
+        Err(e @ FetchError::Cached(_)) => {
+            eprintln!("{}", e);
+            Ok(false)
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn submit(
+    year: u32,
+    day: u32,
+    part: u32,
+    variant: Option<String>,
+    path: Option<PathBuf>,
+) -> Result<bool, String> {
+    let solution = registry::find(year, day, part, variant.as_deref())
+        .ok_or_else(|| format!("no such solver for {} day {} part {}", year, day, part))?;
+    let mut submitter = Submitter::from_env()?;
+    let input = read_input(year, day, &path)?;
+    let answer = match Outcome::of(solution, &input) {
+        Outcome::Solved(answer) => answer.to_string(),
+        outcome => {
+            eprintln!("{}: {}", solution.name(), outcome);
+            return Ok(false);
+        }
+    };
+    println!("{}: {}", solution.name(), answer);
+    match submitter.submit(year, day, part, &answer) {
+        Ok(verdict) => {
+            println!("{}", verdict);
+            Ok(verdict == Verdict::Accepted)
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            Ok(false)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let verbosity = match cli.trace_file {
+        Some(_) => cli.verbose.max(1),
+        None => cli.verbose,
+    };
+    let year = cli.year.unwrap_or_else(registry::latest_year);
+    if registry::solutions(year).is_empty() {
+        let years: Vec<String> = registry::years().map(|y| y.to_string()).collect();
+        eprintln!(
+            "error: no season {}; try one of: {}",
+            year,
+            years.join(", ")
+        );
+        return ExitCode::from(2);
+    }
+    if let Err(e) = trace::init(year, &cli.trace_days, verbosity, cli.trace_file.as_deref()) {
+        eprintln!("error: could not start tracing: {}", e);
+        return ExitCode::from(2);
+    }
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            variant,
+            input,
+            format,
+        } => run(year, day, part, variant, input, format),
+        Command::Bench {
+            day,
+            part,
+            input,
+            warmup,
+            samples,
+            baseline,
+            threshold,
+            format,
+        } => bench(
+            year, day, part, input, warmup, samples, baseline, threshold, format,
+        ),
+        Command::Verify { format } => verify(year, format),
+        Command::Generate {
+            day,
+            size,
+            seed,
+            format,
+        } => generate(year, day, size, seed, format),
+        Command::RunAll { threads, format } => run_all(year, threads, format),
+        Command::Explain { day, part } => explain(year, day, part),
+        Command::Diff { day, part, input } => diff(year, day, part, input),
+        Command::Fetch { day } => fetch(year, day),
+        Command::Submit {
+            day,
+            part,
+            variant,
+            input,
+        } => submit(year, day, part, variant, input),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
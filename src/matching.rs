@@ -0,0 +1,357 @@
+//! Assigning each key a distinct value from its own set of candidates, e.g. ticket fields to columns
+//! (2020 day 16) or allergens to ingredients (2020 day 21).
+//!
+//! `solve` first propagates (a key with a single candidate takes it, and no other key can), which is
+//! all the puzzles need. Anything left over goes to a maximum matching (Hopcroft–Karp), which either
+//! finds an assignment or proves there's none, and then to a backtracking search for a second one.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+
+/// The candidate values for each key.
+pub type Candidates<K, V> = HashMap<K, HashSet<V>>;
+
+pub type Assignment<K, V> = BTreeMap<K, V>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution<K, V> {
+    Unique(Assignment<K, V>),
+    /// Two of the assignments that fit.
+    Multiple(Assignment<K, V>, Assignment<K, V>),
+    Unsatisfiable(Conflict<K, V>),
+}
+
+/// Keys that can't all be assigned, since between them they only have `values` to choose from,
+/// and there are fewer of those than keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict<K, V> {
+    pub keys: Vec<K>,
+    pub values: Vec<V>,
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Display for Conflict<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the {} keys {:?} only have the {} values {:?} between them",
+            self.keys.len(),
+            self.keys,
+            self.values.len(),
+            self.values
+        )
+    }
+}
+
+impl<K: fmt::Debug + Ord, V: fmt::Debug + PartialEq> fmt::Display for Solution<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Solution::Unique(a) => write!(f, "unique: {:?}", a),
+            Solution::Multiple(a, b) => {
+                let differ: Vec<&K> = a
+                    .iter()
+                    .filter(|(k, v)| b.get(k) != Some(v))
+                    .map(|(k, _)| k)
+                    .collect();
+                write!(f, "ambiguous: {:?} could go either way", differ)
+            }
+            Solution::Unsatisfiable(conflict) => write!(f, "unsatisfiable: {}", conflict),
+        }
+    }
+}
+
+/// Assign every key, saying whether that can be done one way, several ways, or not at all.
+pub fn solve<K, V>(candidates: &Candidates<K, V>) -> Solution<K, V>
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + Eq + Hash + Ord,
+{
+    let (assignment, rest) = propagate(candidates);
+    if rest.is_empty() && assignment.len() == candidates.len() {
+        return Solution::Unique(assignment);
+    }
+    let graph = Graph::new(candidates);
+    let matching = graph.max_matching();
+    if let Some(key) = matching.iter().position(|m| m.is_none()) {
+        return Solution::Unsatisfiable(graph.conflict(&matching, key));
+    }
+    let mut found = graph.solutions(2);
+    match (found.pop(), found.pop()) {
+        (Some(second), Some(first)) => {
+            Solution::Multiple(graph.assignment(&first), graph.assignment(&second))
+        }
+        _ => Solution::Unique(graph.assignment(&matching)),
+    }
+}
+
+/// Repeatedly assign keys that have a single candidate left, returning those assignments and the
+/// candidates that remain for everything else. Stops early if two keys need the same value.
+pub fn propagate<K, V>(candidates: &Candidates<K, V>) -> (Assignment<K, V>, Candidates<K, V>)
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + Eq + Hash,
+{
+    let mut rest = candidates.clone();
+    let mut assignment = BTreeMap::new();
+    while let Some((key, value)) = rest
+        .iter()
+        .find(|(_, vs)| vs.len() == 1)
+        .map(|(k, vs)| (k.clone(), vs.iter().next().unwrap().clone()))
+    {
+        rest.remove(&key);
+        for vs in rest.values_mut() {
+            vs.remove(&value);
+        }
+        assignment.insert(key, value);
+    }
+    (assignment, rest)
+}
+
+/// Up to `limit` distinct complete assignments, by backtracking over the most constrained key first.
+pub fn solutions<K, V>(candidates: &Candidates<K, V>, limit: usize) -> Vec<Assignment<K, V>>
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + Eq + Hash + Ord,
+{
+    let graph = Graph::new(candidates);
+    graph
+        .solutions(limit)
+        .iter()
+        .map(|m| graph.assignment(m))
+        .collect()
+}
+
+/// The values each key takes in at least one complete assignment; none at all if there isn't one.
+pub fn possible_values<K, V>(candidates: &Candidates<K, V>) -> BTreeMap<K, BTreeSet<V>>
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + Eq + Hash + Ord,
+{
+    let graph = Graph::new(candidates);
+    let mut partial: Matching = vec![None; graph.keys.len()];
+    let mut free = vec![true; graph.values.len()];
+    (0..graph.keys.len())
+        .map(|k| {
+            let values = graph.adj[k]
+                .iter()
+                .filter(|&&v| {
+                    partial[k] = Some(v);
+                    free[v] = false;
+                    let complete = graph
+                        .extend_matching(&partial, &free)
+                        .iter()
+                        .all(|m| m.is_some());
+                    partial[k] = None;
+                    free[v] = true;
+                    complete
+                })
+                .map(|v| graph.values[*v].clone())
+                .collect();
+            (graph.keys[k].clone(), values)
+        })
+        .collect()
+}
+
+/// As many keys as possible, each assigned a distinct candidate.
+pub fn max_matching<K, V>(candidates: &Candidates<K, V>) -> Assignment<K, V>
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + Eq + Hash + Ord,
+{
+    let graph = Graph::new(candidates);
+    let matching = graph.max_matching();
+    graph.assignment(&matching)
+}
+
+// The candidates as a bipartite graph between key and value indices, in sorted order so that
+// results don't depend on hashing.
+struct Graph<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    adj: Vec<Vec<usize>>,
+}
+
+// The value index matched to each key, if any.
+type Matching = Vec<Option<usize>>;
+
+impl<K, V> Graph<K, V>
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + Eq + Hash + Ord,
+{
+    fn new(candidates: &HashMap<K, HashSet<V>>) -> Graph<K, V> {
+        let mut keys: Vec<K> = candidates.keys().cloned().collect();
+        keys.sort();
+        let values: BTreeSet<&V> = candidates.values().flatten().collect();
+        let index: HashMap<&V, usize> = values.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let adj = keys
+            .iter()
+            .map(|k| {
+                let mut vs: Vec<usize> = candidates[k].iter().map(|v| index[v]).collect();
+                vs.sort_unstable();
+                vs
+            })
+            .collect();
+        Graph {
+            keys,
+            values: values.into_iter().cloned().collect(),
+            adj,
+        }
+    }
+
+    fn assignment(&self, matching: &Matching) -> Assignment<K, V> {
+        matching
+            .iter()
+            .enumerate()
+            .filter_map(|(k, v)| Some((self.keys[k].clone(), self.values[(*v)?].clone())))
+            .collect()
+    }
+
+    fn max_matching(&self) -> Matching {
+        self.extend_matching(&vec![None; self.keys.len()], &vec![true; self.values.len()])
+    }
+
+    // Hopcroft–Karp, leaving keys already matched in `fixed` alone and only using `free` values.
+    fn extend_matching(&self, fixed: &[Option<usize>], free: &[bool]) -> Matching {
+        let n = self.keys.len();
+        let mut key_to_value: Matching = fixed.to_vec();
+        let mut value_to_key: Vec<Option<usize>> = vec![None; self.values.len()];
+        let open: Vec<bool> = fixed.iter().map(|m| m.is_none()).collect();
+        loop {
+            // Layer the open keys by their distance from an unmatched one along alternating paths.
+            let mut dist: Vec<Option<usize>> = vec![None; n];
+            let mut queue = VecDeque::new();
+            for k in (0..n).filter(|k| open[*k] && key_to_value[*k].is_none()) {
+                dist[k] = Some(0);
+                queue.push_back(k);
+            }
+            let mut found = false;
+            while let Some(k) = queue.pop_front() {
+                for &v in self.adj[k].iter().filter(|v| free[**v]) {
+                    match value_to_key[v] {
+                        None => found = true,
+                        Some(next) if dist[next].is_none() => {
+                            dist[next] = Some(dist[k].unwrap() + 1);
+                            queue.push_back(next);
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+            if !found {
+                return key_to_value;
+            }
+            let unmatched: Vec<usize> = (0..n)
+                .filter(|k| open[*k] && key_to_value[*k].is_none())
+                .collect();
+            for k in unmatched {
+                self.augment(k, free, &mut dist, &mut key_to_value, &mut value_to_key);
+            }
+        }
+    }
+
+    fn augment(
+        &self,
+        k: usize,
+        free: &[bool],
+        dist: &mut [Option<usize>],
+        key_to_value: &mut [Option<usize>],
+        value_to_key: &mut [Option<usize>],
+    ) -> bool {
+        for &v in self.adj[k].iter().filter(|v| free[**v]) {
+            let next = value_to_key[v];
+            let advances = match next {
+                None => true,
+                Some(next) => {
+                    dist[next] == dist[k].map(|d| d + 1)
+                        && self.augment(next, free, dist, key_to_value, value_to_key)
+                }
+            };
+            if advances {
+                key_to_value[k] = Some(v);
+                value_to_key[v] = Some(k);
+                return true;
+            }
+        }
+        // A dead end, so don't look here again this round.
+        dist[k] = None;
+        false
+    }
+
+    // Hall's theorem: `key` is unmatched in a maximum matching, so the keys reachable from it along
+    // alternating paths have exactly one fewer candidate value between them than there are keys.
+    fn conflict(&self, matching: &Matching, key: usize) -> Conflict<K, V> {
+        let mut value_to_key: Vec<Option<usize>> = vec![None; self.values.len()];
+        for (k, v) in matching.iter().enumerate() {
+            if let Some(v) = v {
+                value_to_key[*v] = Some(k);
+            }
+        }
+        let mut keys: BTreeSet<usize> = BTreeSet::new();
+        let mut values: BTreeSet<usize> = BTreeSet::new();
+        let mut stack = vec![key];
+        while let Some(k) = stack.pop() {
+            if !keys.insert(k) {
+                continue;
+            }
+            for &v in self.adj[k].iter() {
+                if values.insert(v) {
+                    stack.extend(value_to_key[v]);
+                }
+            }
+        }
+        Conflict {
+            keys: keys.into_iter().map(|k| self.keys[k].clone()).collect(),
+            values: values.into_iter().map(|v| self.values[v].clone()).collect(),
+        }
+    }
+
+    fn solutions(&self, limit: usize) -> Vec<Matching> {
+        let mut found = Vec::new();
+        let mut partial: Matching = vec![None; self.keys.len()];
+        let mut free = vec![true; self.values.len()];
+        self.search(&mut partial, &mut free, limit, &mut found);
+        found
+    }
+
+    fn search(
+        &self,
+        partial: &mut [Option<usize>],
+        free: &mut [bool],
+        limit: usize,
+        found: &mut Vec<Matching>,
+    ) {
+        if found.len() >= limit {
+            return;
+        }
+        // Give up on this branch as soon as the rest can't all be matched.
+        let completed = self.extend_matching(partial, free);
+        if completed.iter().any(|m| m.is_none()) {
+            return;
+        }
+        let options = |k: usize| self.adj[k].iter().filter(|v| free[**v]).count();
+        let key = match (0..partial.len())
+            .filter(|k| partial[*k].is_none())
+            .min_by_key(|k| options(*k))
+        {
+            Some(key) => key,
+            None => {
+                found.push(partial.to_vec());
+                return;
+            }
+        };
+        for &v in self.adj[key].iter() {
+            if !free[v] {
+                continue;
+            }
+            partial[key] = Some(v);
+            free[v] = false;
+            self.search(partial, free, limit, found);
+            free[v] = true;
+            partial[key] = None;
+            if found.len() >= limit {
+                return;
+            }
+        }
+    }
+}
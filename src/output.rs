@@ -0,0 +1,69 @@
+//! Machine-readable run results, for scripts and dashboards that shouldn't have to scrape the
+//! human-readable output.
+
+use crate::differential::Outcome;
+use crate::error::ParseError;
+use crate::input;
+use crate::registry::{Answer, Solution};
+use serde::Serialize;
+use std::time::Duration;
+
+/// One solver's run, e.g.
+/// `{"year":2020,"day":1,"part":1,"variant":"for_hash","answer":744475,"elapsed_ns":37550,"error":null}`
+#[derive(Debug, Clone, Serialize)]
+pub struct RunResult {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    pub answer: Option<Answer>,
+    pub elapsed_ns: u64,
+    pub error: Option<Diagnostic>,
+}
+
+/// Why a run has no answer, tagged with its `kind`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Diagnostic {
+    /// `{"kind":"parse","day":8,"line":2,"column":1,"text":"xyz","span":3,"message":"..."}`
+    Parse(ParseError),
+    Panic {
+        message: String,
+    },
+    MissingInput {
+        path: String,
+    },
+}
+
+impl RunResult {
+    /// `outcome` is None when there was no input to run on.
+    pub fn new(solution: &Solution, outcome: Option<&Outcome>, elapsed: Duration) -> RunResult {
+        let (answer, error) = match outcome {
+            Some(Outcome::Solved(answer)) => (Some(answer.clone()), None),
+            Some(Outcome::Rejected(e)) => (None, Some(Diagnostic::Parse(e.clone()))),
+            Some(Outcome::Panicked(message)) => (
+                None,
+                Some(Diagnostic::Panic {
+                    message: message.clone(),
+                }),
+            ),
+            None => (
+                None,
+                Some(Diagnostic::MissingInput {
+                    path: input::path(solution.year, solution.day)
+                        .display()
+                        .to_string(),
+                }),
+            ),
+        };
+        RunResult {
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            variant: solution.variant,
+            answer,
+            elapsed_ns: elapsed.as_nanos() as u64,
+            error,
+        }
+    }
+}
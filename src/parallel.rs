@@ -0,0 +1,149 @@
+use crate::differential::Outcome;
+use crate::registry::{Solution, Solver};
+use crate::verify::{Manifest, Status};
+use cpu_time::ThreadTime;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Anything using at least this share of the total CPU time is flagged in the table.
+const HEAVY_SHARE: f64 = 0.05;
+
+/// What running one solver cost.
+pub struct Timing {
+    pub solution: &'static Solution,
+    /// None if there was no input for the day.
+    pub outcome: Option<Outcome>,
+    pub wall: Duration,
+    pub cpu: Duration,
+}
+
+/// Run every solver once, spread over `threads` worker threads, and time each of them.
+///
+/// Inputs are read up front, once per year and day. Timings come back in the order given.
+pub fn run_all<I, F>(solutions: I, read_input: F, threads: usize) -> Vec<Timing>
+where
+    I: IntoIterator<Item = &'static Solution>,
+    F: Fn(u32, u32) -> Option<String>,
+{
+    let solutions: Vec<&'static Solution> = solutions.into_iter().collect();
+    let mut inputs: BTreeMap<(u32, u32), Option<String>> = BTreeMap::new();
+    for s in solutions.iter() {
+        inputs.entry((s.year, s.day)).or_insert_with(|| read_input(s.year, s.day));
+    }
+
+    let next = AtomicUsize::new(0);
+    let timings: Mutex<Vec<Option<Timing>>> = Mutex::new(solutions.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let s = match solutions.get(i) {
+                    Some(s) => *s,
+                    None => break,
+                };
+                let input = inputs[&(s.year, s.day)].as_deref();
+                let (wall, cpu) = (Instant::now(), ThreadTime::now());
+                let outcome = input.map(|input| Outcome::of(s, input));
+                let timing = Timing {
+                    solution: s,
+                    outcome,
+                    wall: wall.elapsed(),
+                    cpu: cpu.elapsed(),
+                };
+                timings.lock().unwrap()[i] = Some(timing);
+            });
+        }
+    });
+    timings.into_inner().unwrap().into_iter().map(Option::unwrap).collect()
+}
+
+/// A summary of a run, most expensive solver first.
+pub struct Table<'a> {
+    pub timings: Vec<&'a Timing>,
+    pub manifest: Option<&'a Manifest>,
+    /// How long the whole run took, as opposed to the sum of its parts.
+    pub elapsed: Duration,
+}
+
+impl<'a> Table<'a> {
+    pub fn new(timings: &'a [Timing], manifest: Option<&'a Manifest>, elapsed: Duration) -> Table<'a> {
+        let mut timings: Vec<&Timing> = timings.iter().collect();
+        timings.sort_by(|a, b| b.cpu.cmp(&a.cpu).then(b.wall.cmp(&a.wall)));
+        Table { timings, manifest, elapsed }
+    }
+
+    pub fn total_cpu(&self) -> Duration {
+        self.timings.iter().map(|t| t.cpu).sum()
+    }
+
+    /// Unlike `verify`, a solver that errors or panics fails even with no expected answer.
+    pub fn status(&self, timing: &Timing) -> Status {
+        let s = timing.solution;
+        let expected = self.manifest.filter(|m| m.year == s.year).and_then(|m| m.expected(s.day, s.part));
+        match (expected, &timing.outcome) {
+            (_, Some(Outcome::Rejected(_))) | (_, Some(Outcome::Panicked(_))) => Status::Fail,
+            (Some(expected), Some(Outcome::Solved(answer))) if *expected != answer.to_string() => Status::Fail,
+            (Some(_), Some(Outcome::Solved(_))) => Status::Pass,
+            _ => Status::Missing,
+        }
+    }
+
+    pub fn ok(&self) -> bool {
+        self.timings.iter().all(|t| self.status(t) != Status::Fail)
+    }
+
+    pub fn is_heavy(&self, timing: &Timing) -> bool {
+        timing.cpu.as_secs_f64() >= HEAVY_SHARE * self.total_cpu().as_secs_f64()
+    }
+}
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total_cpu().as_secs_f64().max(f64::MIN_POSITIVE);
+        let width = self.timings.iter().map(|t| t.solution.name().len()).max().unwrap_or(0);
+        writeln!(
+            f,
+            "  {:<width$}  {:>12}  {:>12}  {:>6}  {:<6} answer",
+            "solver",
+            "wall",
+            "cpu",
+            "share",
+            "status",
+            width = width
+        )?;
+        for t in self.timings.iter() {
+            let answer = match &t.outcome {
+                Some(outcome) => outcome.to_string(),
+                None => "no input".to_string(),
+            };
+            writeln!(
+                f,
+                "{} {:<width$}  {:>12}  {:>12}  {:>5.1}%  {:<6} {}",
+                if self.is_heavy(t) { "*" } else { " " },
+                t.solution.name(),
+                format!("{:.2?}", t.wall),
+                format!("{:.2?}", t.cpu),
+                100.0 * t.cpu.as_secs_f64() / total,
+                match self.status(t) {
+                    Status::Pass => "PASS",
+                    Status::Fail => "FAIL",
+                    Status::Missing => "-",
+                },
+                answer,
+                width = width
+            )?;
+        }
+        write!(
+            f,
+            "{} solvers in {:.2?} ({:.2?} of CPU); * marks anything over {}% of the CPU time",
+            self.timings.len(),
+            self.elapsed,
+            self.total_cpu(),
+            HEAVY_SHARE * 100.0
+        )
+    }
+}
@@ -0,0 +1,201 @@
+use crate::error::ParseError;
+use crate::y2020;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A solver's result. Days variously return i32, i64, u64, usize and String, so widen to these.
+///
+/// Serializes as a bare JSON number or string. Reading one back gives `Int` for any number that
+/// fits in an i64, whichever variant it was written from, so numbers compare by value: `Int(5)`
+/// equals `UInt(5)`.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+}
+
+impl Answer {
+    fn number(&self) -> Option<i128> {
+        match self {
+            Answer::Int(v) => Some(*v as i128),
+            Answer::UInt(v) => Some(*v as i128),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => self.number().is_some() && self.number() == other.number(),
+        }
+    }
+}
+
+// Hashes by value like `eq` compares, so `Int(5)` and `UInt(5)` land together.
+impl Hash for Answer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Answer::Text(v) => v.hash(state),
+            _ => self.number().hash(state),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::UInt(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(v: i32) -> Answer {
+        Answer::Int(v as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Answer {
+        Answer::Int(v)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Answer {
+        Answer::UInt(v)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Answer {
+        Answer::UInt(v as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Answer {
+        Answer::Text(v)
+    }
+}
+
+/// Anything that can solve one part of one day's puzzle.
+pub trait Solver: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn part(&self) -> u32;
+    /// The aoc_runner variant name, e.g. `for_hash` for `#[aoc(day1, part1, for_hash)]`.
+    fn variant(&self) -> Option<&'static str>;
+    fn solve(&self, input: &str) -> Result<Answer, ParseError>;
+
+    fn name(&self) -> String {
+        match self.variant() {
+            Some(v) => format!("y{}::day{}::part{}::{}", self.year(), self.day(), self.part(), v),
+            None => format!("y{}::day{}::part{}", self.year(), self.day(), self.part()),
+        }
+    }
+}
+
+/// A registered `#[aoc]` function.
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    pub run: fn(&str) -> Result<Answer, ParseError>,
+}
+
+impl Solver for Solution {
+    fn year(&self) -> u32 {
+        self.year
+    }
+
+    fn day(&self) -> u32 {
+        self.day
+    }
+
+    fn part(&self) -> u32 {
+        self.part
+    }
+
+    fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, ParseError> {
+        // Match aoc_runner, which strips trailing newlines before handing over the input.
+        (self.run)(input.trim_end_matches('\n'))
+    }
+}
+
+macro_rules! solution {
+    ($year:expr, $day:expr, $part:expr, $f:path) => {
+        solution!(@ $year, $day, $part, None, $f)
+    };
+    ($year:expr, $day:expr, $part:expr, $variant:ident, $f:path) => {
+        solution!(@ $year, $day, $part, Some(stringify!($variant)), $f)
+    };
+    (@ $year:expr, $day:expr, $part:expr, $variant:expr, $f:path) => {
+        $crate::registry::Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            variant: $variant,
+            run: |input| $f(input).map($crate::registry::Answer::from),
+        }
+    };
+}
+pub(crate) use solution;
+
+/// Every season in the crate, oldest first. To add one, create a `yYYYY` module next to `y2020`
+/// with its days and a `SOLUTIONS` table, put its inputs under `input/YYYY/`, and list it here.
+pub static YEARS: &[(u32, &[Solution])] = &[(2020, y2020::SOLUTIONS)];
+
+pub fn years() -> impl Iterator<Item = u32> {
+    YEARS.iter().map(|(year, _)| *year)
+}
+
+/// The season commands default to when no year is given.
+pub fn latest_year() -> u32 {
+    years().max().unwrap()
+}
+
+/// Every solver registered for `year`, or none if there is no such season.
+pub fn solutions(year: u32) -> &'static [Solution] {
+    YEARS.iter().find(|(y, _)| *y == year).map_or(&[], |(_, solutions)| solutions)
+}
+
+/// Every solver in every season.
+pub fn all() -> impl Iterator<Item = &'static Solution> {
+    YEARS.iter().flat_map(|(_, solutions)| solutions.iter())
+}
+
+/// The days of `year` with at least one solver, in order.
+pub fn days(year: u32) -> Vec<u32> {
+    let mut days: Vec<u32> = solutions(year).iter().map(|s| s.day).collect();
+    days.sort();
+    days.dedup();
+    days
+}
+
+/// All variants registered for a given day and part.
+pub fn variants(year: u32, day: u32, part: u32) -> impl Iterator<Item = &'static Solution> {
+    solutions(year).iter().filter(move |s| s.day == day && s.part == part)
+}
+
+/// Look up a solver. Without a `variant`, the first one registered for the day and part is used.
+pub fn find(year: u32, day: u32, part: u32, variant: Option<&str>) -> Option<&'static Solution> {
+    variants(year, day, part).find(|s| variant.is_none() || s.variant == variant)
+}
+
+/// Run a solver on an arbitrary input, or None if no such solver is registered.
+pub fn solve(year: u32, day: u32, part: u32, variant: Option<&str>, input: &str) -> Option<Result<Answer, ParseError>> {
+    find(year, day, part, variant).map(|s| s.solve(input))
+}
@@ -0,0 +1,90 @@
+use std::env;
+use std::fmt;
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// Identify ourselves, as the site asks of automated tools.
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// The puzzle site, logged in with a session cookie.
+///
+/// Reads `AOC_BASE_URL` and `AOC_SESSION` from the environment by default; the base URL only needs
+/// overriding to talk to a stand-in server.
+pub struct Remote {
+    pub base_url: String,
+    pub session: Option<String>,
+    agent: ureq::Agent,
+}
+
+#[derive(Debug)]
+pub enum RemoteError {
+    /// Nothing to log in with.
+    NoSession,
+    Status(u16, String),
+    Transport(String),
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteError::NoSession => write!(f, "no session token; set AOC_SESSION to your session cookie"),
+            RemoteError::Status(status, body) => {
+                write!(f, "server said {}: {}", status, body.lines().next().unwrap_or(""))
+            }
+            RemoteError::Transport(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Remote {
+    pub fn new(base_url: &str, session: Option<String>) -> Remote {
+        Remote {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    pub fn from_env() -> Remote {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session = env::var("AOC_SESSION").ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        Remote::new(&base_url, session)
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn cookie(&self) -> Result<String, RemoteError> {
+        match &self.session {
+            Some(session) => Ok(format!("session={}", session)),
+            None => Err(RemoteError::NoSession),
+        }
+    }
+
+    /// GET `path` (e.g. "/2020/day/1/input"), returning the body.
+    pub fn get(&self, path: &str) -> Result<String, RemoteError> {
+        let request = self.agent.get(&self.url(path)).set("Cookie", &self.cookie()?);
+        read_response(request.call())
+    }
+
+    /// POST a form to `path`, returning the body.
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String, RemoteError> {
+        let request = self.agent.post(&self.url(path)).set("Cookie", &self.cookie()?);
+        read_response(request.send_form(fields))
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, RemoteError> {
+    match response {
+        Ok(response) => response.into_string().map_err(|e| RemoteError::Transport(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            Err(RemoteError::Status(status, response.into_string().unwrap_or_default()))
+        }
+        Err(e) => Err(RemoteError::Transport(e.to_string())),
+    }
+}
@@ -0,0 +1,302 @@
+use crate::input;
+use crate::remote::{Remote, RemoteError};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The site asks for a minute's pause after a wrong answer, unless it says otherwise.
+const DEFAULT_WAIT: Duration = Duration::from_secs(60);
+
+/// What the site made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the last attempt; nothing was checked.
+    Throttled,
+    /// Already solved, or part 1 isn't solved yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn parse(body: &str) -> Verdict {
+        if body.contains("That's the right answer") {
+            Verdict::Accepted
+        } else if body.contains("You gave an answer too recently") {
+            Verdict::Throttled
+        } else if body.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if body.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the answer is known not to be the solution.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+
+    fn token(self) -> &'static str {
+        match self {
+            Verdict::Accepted => "accepted",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::Throttled => "throttled",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Verdict> {
+        [
+            Verdict::Accepted,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+            Verdict::Throttled,
+            Verdict::WrongLevel,
+            Verdict::Unknown,
+        ]
+        .iter()
+        .cloned()
+        .find(|v| v.token() == token)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.token())
+    }
+}
+
+/// How long the site wants us to hold off, e.g. "You have 1m 5s left to wait" or "please wait 5
+/// minutes before trying again".
+pub fn parse_wait(body: &str) -> Option<Duration> {
+    lazy_static! {
+        static ref LEFT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        static ref MINUTES: Regex = Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap();
+    }
+    if let Some(c) = LEFT.captures(body) {
+        let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+        let seconds: u64 = c[2].parse().unwrap();
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+    MINUTES.captures(body).map(|c| {
+        let minutes: u64 = if &c[1] == "one" { 1 } else { c[1].parse().unwrap() };
+        Duration::from_secs(minutes * 60)
+    })
+}
+
+/// A single submission, as recorded in the history file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// Seconds since the epoch.
+    pub time: u64,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub verdict: Verdict,
+    /// How long to hold off before the next attempt at this part.
+    pub wait: Duration,
+    pub answer: String,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            self.time,
+            self.year,
+            self.day,
+            self.part,
+            self.verdict,
+            self.wait.as_secs(),
+            self.answer
+        )
+    }
+}
+
+impl Attempt {
+    fn parse(line: &str) -> Option<Attempt> {
+        let mut fields = line.splitn(7, ' ');
+        Some(Attempt {
+            time: fields.next()?.parse().ok()?,
+            year: fields.next()?.parse().ok()?,
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            verdict: Verdict::from_token(fields.next()?)?,
+            wait: Duration::from_secs(fields.next()?.parse().ok()?),
+            answer: fields.next()?.to_string(),
+        })
+    }
+}
+
+/// Why an answer wasn't sent.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyAccepted(String),
+    /// The answer was rejected before, or is beyond an answer that was too high or too low.
+    KnownWrong(Attempt),
+    TooSoon(Duration),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyAccepted(answer) => write!(f, "already solved, with {}", answer),
+            Refusal::KnownWrong(a) if a.verdict == Verdict::Wrong => {
+                write!(f, "{} was already rejected", a.answer)
+            }
+            Refusal::KnownWrong(a) => write!(f, "can't be right, since {} was {}", a.answer, a.verdict),
+            Refusal::TooSoon(left) => write!(f, "the site wants us to wait another {}s", left.as_secs()),
+        }
+    }
+}
+
+/// Every attempt so far, one per line of a text file (`input/submissions.txt` by default).
+///
+/// Each line is `<time> <year> <day> <part> <verdict> <wait seconds> <answer>`.
+pub struct History {
+    pub path: PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        PathBuf::from(input::INPUT_DIR).join("submissions.txt")
+    }
+
+    /// Loads the history at `path`, which needn't exist yet.
+    pub fn load(path: PathBuf) -> Result<History, String> {
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let attempts = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                Attempt::parse(line).ok_or_else(|| format!("{}:{}: malformed attempt", path.display(), i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(History { path, attempts })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", attempt)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Whether `answer` is worth sending at time `now`, judging by earlier attempts.
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &str, now: u64) -> Result<(), Refusal> {
+        let attempts: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|a| a.year == year && a.day == day && a.part == part)
+            .collect();
+        if let Some(a) = attempts.iter().find(|a| a.verdict == Verdict::Accepted) {
+            return Err(Refusal::AlreadyAccepted(a.answer.clone()));
+        }
+        let number = answer.parse::<i128>().ok();
+        let known_wrong = attempts.iter().find(|a| {
+            let bound = a.answer.parse::<i128>().ok();
+            match (a.verdict, number, bound) {
+                (Verdict::TooHigh, Some(n), Some(b)) => n >= b,
+                (Verdict::TooLow, Some(n), Some(b)) => n <= b,
+                (verdict, _, _) => verdict.is_wrong() && a.answer == answer,
+            }
+        });
+        if let Some(a) = known_wrong {
+            return Err(Refusal::KnownWrong((*a).clone()));
+        }
+        if let Some(last) = attempts.last() {
+            let until = last.time + last.wait.as_secs();
+            if now < until {
+                return Err(Refusal::TooSoon(Duration::from_secs(until - now)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Remote(RemoteError),
+    History(io::Error),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Refused(r) => write!(f, "not submitting: {}", r),
+            SubmitError::Remote(e) => write!(f, "could not submit: {}", e),
+            SubmitError::History(e) => write!(f, "submitted, but could not record it: {}", e),
+        }
+    }
+}
+
+pub struct Submitter {
+    pub remote: Remote,
+    pub history: History,
+}
+
+impl Submitter {
+    pub fn new(remote: Remote, history: History) -> Submitter {
+        Submitter { remote, history }
+    }
+
+    /// Configured from the environment (see `Remote::from_env`), with the default history file.
+    pub fn from_env() -> Result<Submitter, String> {
+        Ok(Submitter::new(Remote::from_env(), History::load(History::default_path())?))
+    }
+
+    /// Send an answer, unless the history says it would be pointless, and record the outcome.
+    pub fn submit(&mut self, year: u32, day: u32, part: u32, answer: &str) -> Result<Verdict, SubmitError> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.history.check(year, day, part, answer, now).map_err(SubmitError::Refused)?;
+        let level = part.to_string();
+        let body = self
+            .remote
+            .post_form(&format!("/{}/day/{}/answer", year, day), &[("level", &level), ("answer", answer)])
+            .map_err(SubmitError::Remote)?;
+        let verdict = Verdict::parse(&body);
+        let wait = match verdict {
+            v if v == Verdict::Throttled || v.is_wrong() => parse_wait(&body).unwrap_or(DEFAULT_WAIT),
+            _ => Duration::from_secs(0),
+        };
+        let attempt = Attempt {
+            time: now,
+            year,
+            day,
+            part,
+            verdict,
+            wait,
+            answer: answer.to_string(),
+        };
+        self.history.record(attempt).map_err(SubmitError::History)?;
+        Ok(verdict)
+    }
+}
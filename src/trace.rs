@@ -0,0 +1,60 @@
+//! Opt-in instrumentation for the solvers.
+//!
+//! Solvers wrap their phases in `debug` spans (e.g. "parse", "layout_tiles", "find_all") and log
+//! intermediate values as `debug` or `trace` events, all under their own module's target
+//! (`aoc_2020::y2020::day20`, ...). Nothing is recorded unless a subscriber is installed with `init`, so
+//! timing runs stay quiet.
+
+use std::fs::File;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// `-v` shows phase timings and debug events, `-vv` and up also trace events.
+pub fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Enables `level` for the given days of `year`, or for every day of every year if `days` is empty.
+pub fn filter(year: u32, days: &[u32], level: LevelFilter) -> EnvFilter {
+    let directives: Vec<String> = match days {
+        [] => vec![format!("{}={}", env!("CARGO_CRATE_NAME"), level)],
+        _ => days
+            .iter()
+            .map(|d| format!("{}::y{}::day{}={}", env!("CARGO_CRATE_NAME"), year, d, level))
+            .collect(),
+    };
+    EnvFilter::builder()
+        .with_default_directive(LevelFilter::OFF.into())
+        .parse_lossy(directives.join(","))
+}
+
+/// Installs a global subscriber writing to stderr, or to `file` if given. Does nothing at
+/// verbosity 0.
+pub fn init(year: u32, days: &[u32], verbosity: u8, file: Option<&Path>) -> io::Result<()> {
+    if verbosity == 0 {
+        return Ok(());
+    }
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter(year, days, level(verbosity)))
+        // Closing a span logs how long the phase took.
+        .with_span_events(FmtSpan::CLOSE);
+    let result = match file {
+        Some(path) => builder
+            .with_ansi(false)
+            .with_writer(Mutex::new(File::create(path)?))
+            .try_init(),
+        None => builder
+            .with_ansi(io::stderr().is_terminal())
+            .with_writer(io::stderr)
+            .try_init(),
+    };
+    result.map_err(io::Error::other)
+}
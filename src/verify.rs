@@ -0,0 +1,139 @@
+use crate::differential::Outcome;
+use crate::input;
+use crate::registry::{self, Solution};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Known-correct answers for a year, stored next to its inputs as `answers.txt`.
+///
+/// Each non-blank, non-`#` line is `<day> <part> <answer>`.
+pub struct Manifest {
+    pub year: u32,
+    pub answers: BTreeMap<(u32, u32), String>,
+}
+
+impl Manifest {
+    pub fn path(year: u32) -> PathBuf {
+        input::year_dir(year).join("answers.txt")
+    }
+
+    pub fn load(year: u32) -> Result<Manifest, String> {
+        let path = Manifest::path(year);
+        let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Manifest::parse(year, &text).map_err(|e| format!("{}:{}", path.display(), e))
+    }
+
+    pub fn parse(year: u32, text: &str) -> Result<Manifest, String> {
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ' ');
+            let day = fields.next().and_then(|d| d.parse::<u32>().ok());
+            let part = fields.next().and_then(|p| p.parse::<u32>().ok());
+            match (day, part, fields.next()) {
+                (Some(day), Some(part), Some(answer)) => {
+                    if answers.insert((day, part), answer.to_string()).is_some() {
+                        return Err(format!("{}: duplicate answer for day {} part {}", i + 1, day, part));
+                    }
+                }
+                _ => return Err(format!("{}: expected `<day> <part> <answer>`", i + 1)),
+            }
+        }
+        Ok(Manifest { year, answers })
+    }
+
+    pub fn expected(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|a| a.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    Fail,
+    // Nothing recorded in the manifest, or no input to run against.
+    Missing,
+}
+
+pub struct Entry {
+    pub solution: &'static Solution,
+    pub expected: Option<String>,
+    pub outcome: Option<Outcome>,
+    pub elapsed: Duration,
+}
+
+impl Entry {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.outcome) {
+            (Some(expected), Some(Outcome::Solved(answer))) if *expected == answer.to_string() => Status::Pass,
+            (Some(_), Some(_)) => Status::Fail,
+            _ => Status::Missing,
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.solution;
+        match s.variant {
+            Some(v) => write!(f, "Day {} - Part {} - {}: ", s.day, s.part, v)?,
+            None => write!(f, "Day {} - Part {}: ", s.day, s.part)?,
+        }
+        match (self.status(), &self.outcome) {
+            (Status::Pass, _) => write!(f, "PASS ({:?})", self.elapsed),
+            (Status::Fail, Some(outcome)) => write!(
+                f,
+                "FAIL ({:?})\n\texpected: {}\n\tactual:   {}",
+                self.elapsed,
+                self.expected.as_deref().unwrap_or(""),
+                outcome
+            ),
+            (_, None) => write!(f, "MISSING (no input at {})", input::path(s.year, s.day).display()),
+            _ => write!(f, "MISSING (no expected answer recorded)"),
+        }
+    }
+}
+
+/// Run every solver registered for the manifest's year against its checked-in input, and compare
+/// with the manifest.
+pub fn verify(manifest: &Manifest) -> Vec<Entry> {
+    verify_with(manifest, |day| input::read(manifest.year, day).ok())
+}
+
+pub fn verify_with<F: Fn(u32) -> Option<String>>(manifest: &Manifest, read_input: F) -> Vec<Entry> {
+    let mut inputs: BTreeMap<u32, Option<String>> = BTreeMap::new();
+    registry::solutions(manifest.year)
+        .iter()
+        .map(|s| {
+            let input = inputs.entry(s.day).or_insert_with(|| read_input(s.day));
+            let start = Instant::now();
+            let outcome = input.as_ref().map(|input| Outcome::of(s, input));
+            Entry {
+                solution: s,
+                expected: manifest.expected(s.day, s.part).map(|a| a.to_string()),
+                outcome,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect()
+}
+
+/// e.g. "60 passed, 1 failed, 0 missing"
+pub fn summarize(entries: &[Entry]) -> String {
+    let count = |status: Status| entries.iter().filter(|e| e.status() == status).count();
+    format!(
+        "{} passed, {} failed, {} missing",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing)
+    )
+}
+
@@ -0,0 +1,102 @@
+//! The 2020 season.
+
+use crate::registry::{solution, Solution};
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// Every `#[aoc]` function in the season, in the order aoc_runner would run them.
+///
+/// Keep this in sync with the attributes when adding a day or variant.
+pub static SOLUTIONS: &[Solution] = &[
+    solution!(2020, 1, 1, for_hash, day1::solve_part1),
+    solution!(2020, 1, 1, for_loop, day1::solve_part1_for),
+    solution!(2020, 1, 1, k_sum, day1::solve_part1_k_sum),
+    solution!(2020, 1, 1, bitset, day1::solve_part1_bitset),
+    solution!(2020, 1, 2, for_hash, day1::solve_part2),
+    solution!(2020, 1, 2, for_loop, day1::solve_part2_for),
+    solution!(2020, 1, 2, iterclone, day1::solve_part2_iterclone),
+    solution!(2020, 1, 2, k_sum, day1::solve_part2_k_sum),
+    solution!(2020, 1, 2, bitset, day1::solve_part2_bitset),
+    solution!(2020, 2, 1, re_bytes, day2::solve_part1_rebytes),
+    solution!(2020, 2, 1, re_str, day2::solve_part1_restr),
+    solution!(2020, 2, 2, re_str, day2::solve_part2_restr),
+    solution!(2020, 2, 2, re_str_count, day2::solve_part2_restrcount),
+    solution!(2020, 3, 1, day3::solve_part1),
+    solution!(2020, 3, 2, day3::solve_part2),
+    solution!(2020, 4, 1, re_str, day4::solve_part4),
+    solution!(2020, 4, 2, day4::solve_part2),
+    solution!(2020, 4, 2, count, day4::solve_part2_count),
+    solution!(2020, 5, 1, day5::solve_part1),
+    solution!(2020, 5, 1, vscan, day5::solve_part1_vscan),
+    solution!(2020, 5, 2, day5::solve_part2),
+    solution!(2020, 5, 2, hashset, day5::solve_part2_hashset),
+    solution!(2020, 5, 2, window, day5::solve_part2_window),
+    solution!(2020, 6, 1, day6::solve_part1),
+    solution!(2020, 6, 1, ch, day6::solve_part1_ch),
+    solution!(2020, 6, 2, day6::solve_part2),
+    solution!(2020, 6, 2, ch, day6::solve_part2_ch),
+    solution!(2020, 7, 1, day7::solve_part1),
+    solution!(2020, 7, 2, day7::solve_part2),
+    solution!(2020, 8, 1, day8::solve_part1),
+    solution!(2020, 8, 2, day8::solve_part2),
+    solution!(2020, 9, 1, day9::solve_part1),
+    solution!(2020, 9, 2, day9::solve_part2),
+    solution!(2020, 10, 1, day10::solve_part1),
+    solution!(2020, 10, 2, day10::solve_part2),
+    solution!(2020, 11, 1, day11::solve_part1),
+    solution!(2020, 11, 2, day11::solve_part2),
+    solution!(2020, 12, 1, day12::solve_part1),
+    solution!(2020, 12, 2, day12::solve_part2),
+    solution!(2020, 13, 1, day13::solve_part1),
+    solution!(2020, 13, 2, day13::solve_part2),
+    solution!(2020, 13, 2, brute, day13::solve_part2_brute),
+    solution!(2020, 14, 1, day14::solve_part1),
+    solution!(2020, 14, 2, day14::solve_part2),
+    solution!(2020, 15, 1, day15::solve_part1),
+    solution!(2020, 15, 2, day15::solve_part2),
+    solution!(2020, 16, 1, day16::solve_part1),
+    solution!(2020, 16, 2, brute, day16::solve_part2_brute),
+    solution!(2020, 16, 2, no_brute, day16::solve_part2_no_brute),
+    solution!(2020, 17, 1, day17::solve_part1),
+    solution!(2020, 17, 2, day17::solve_part2),
+    solution!(2020, 18, 1, day18::solve_part1),
+    solution!(2020, 18, 2, day18::solve_part2),
+    solution!(2020, 19, 1, day19::solve_part1),
+    solution!(2020, 19, 2, day19::solve_part2),
+    solution!(2020, 20, 1, day20::solve_part1),
+    solution!(2020, 20, 2, day20::solve_part2),
+    solution!(2020, 21, 1, day21::solve_part1),
+    solution!(2020, 21, 2, day21::solve_part2),
+    solution!(2020, 22, 1, day22::solve_part1),
+    solution!(2020, 22, 2, day22::solve_part2),
+    solution!(2020, 23, 1, day23::solve_part1),
+    solution!(2020, 23, 1, linked_vec, day23::solve_part1_linked_vec),
+    solution!(2020, 23, 2, linked_vec, day23::solve_part2_linked_vec),
+    solution!(2020, 24, 1, day24::solve_part1),
+    solution!(2020, 24, 2, day24::solve_part2),
+    solution!(2020, 25, 1, day25::solve_part1),
+];
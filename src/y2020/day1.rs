@@ -0,0 +1,484 @@
+use crate::error::{ParseError, Source};
+use std::collections::{HashMap, HashSet};
+
+fn no_sum(src: &Source, k: usize) -> ParseError {
+    src.missing(format!("expected {} entries that add up to 2020", k))
+}
+
+
+#[aoc(day1, part1, for_hash)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    let src = Source::new(1, input);
+    let hash_set: HashSet<i32> = src.numbers().collect::<Result<_, _>>()?;
+    for v in &hash_set {
+        let w: i32 = 2020 - v;
+        if hash_set.contains(&w) {
+            return Ok(v * w);
+        }
+    }
+    Err(no_sum(&src, 2))
+}
+
+
+#[aoc(day1, part2, for_hash)]
+pub fn solve_part2(input: &str) -> Result<i32, ParseError> {
+    let src = Source::new(1, input);
+    let hash_set: HashSet<i32> = src.numbers().collect::<Result<_, _>>()?;
+    for v in &hash_set {
+        for w in &hash_set {
+            let x: i32 = 2020 - v - w;
+            if hash_set.contains(&x) {
+                return Ok(v * w * x);
+            }
+        }
+    }
+    Err(no_sum(&src, 3))
+}
+
+
+// Sanity checking that the naive solution is in fact the slowest.
+#[aoc(day1, part1, for_loop)]
+pub fn solve_part1_for(input: &str) -> Result<i32, ParseError> {
+    let src = Source::new(1, input);
+    let int_vec: Vec<i32> = src.numbers().collect::<Result<_, _>>()?;
+    for v in &int_vec {
+        for w in &int_vec {
+            if v + w == 2020 {
+                return Ok(v * w);
+            }
+        }
+    }
+    Err(no_sum(&src, 2))
+}
+
+// Sanity checking that the naive solution is in fact the slowest.
+#[aoc(day1, part2, for_loop)]
+pub fn solve_part2_for(input: &str) -> Result<i32, ParseError> {
+    let src = Source::new(1, input);
+    let int_vec: Vec<i32> = src.numbers().collect::<Result<_, _>>()?;
+    for v in &int_vec {
+        for w in &int_vec {
+            for x in &int_vec {
+                if v + w + x == 2020 {
+                    return Ok(v * w * x);
+                }
+            }
+        }
+    }
+    Err(no_sum(&src, 3))
+}
+
+
+// Instead of iterating over n^2 pairs, we can do n(n-1)/2 by starting after
+// the current elment. This is about 16% faster than for_hash soution.
+#[aoc(day1, part2, iterclone)]
+pub fn solve_part2_iterclone(input: &str) -> Result<i32, ParseError> {
+    let src = Source::new(1, input);
+    let hash_set: HashSet<i32> = src.numbers().collect::<Result<_, _>>()?;
+    
+    // I wonder if it would be faster if we had a list and used array indexes.
+    let mut first_iter = hash_set.iter();
+    while let Some(v) = first_iter.next() {
+        for w in first_iter.clone() {
+            let x: i32 = 2020 - v - w;
+            if hash_set.contains(&x) {
+                return Ok(v * w * x);
+            }
+        }
+    }
+    Err(no_sum(&src, 3))
+}
+
+
+/// How `find_k_sum` searches for combinations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Try every combination of k - 1 values, looking the last one up in a hash map: O(n^(k-1)).
+    Hash,
+    /// Sort, try every combination of k - 2 values, and find the last two by walking in from both ends
+    /// of what's left: also O(n^(k-1)), but with less overhead than hashing for k >= 3.
+    TwoPointer,
+    /// Hash the sums of every combination of k / 2 values and match them up with the sums of
+    /// combinations of the other k - k / 2: O(n^ceil(k/2)) time, plus O(n^(k/2)) memory.
+    MeetInTheMiddle,
+}
+
+impl Strategy {
+    /// The strategy that should be quickest for `k` of `n` values.
+    pub fn choose(k: usize, n: usize) -> Strategy {
+        match k {
+            0..=2 => Strategy::Hash,
+            3 => Strategy::TwoPointer,
+            // Storing the halves only pays off once there are enough values to save work.
+            _ if n < 16 => Strategy::TwoPointer,
+            _ => Strategy::MeetInTheMiddle,
+        }
+    }
+}
+
+/// Every set of `k` distinct indices whose values add up to `target`, each in increasing order and
+/// sorted. Equal values at different indices count as different combinations.
+pub fn find_k_sum(values: &[i64], k: usize, target: i64) -> Vec<Vec<usize>> {
+    find_k_sum_with(values, k, target, Strategy::choose(k, values.len()))
+}
+
+pub fn find_k_sum_with(values: &[i64], k: usize, target: i64, strategy: Strategy) -> Vec<Vec<usize>> {
+    if k > values.len() {
+        return vec![];
+    }
+    let mut found = match strategy {
+        Strategy::Hash => k_sum_hash(values, k, target),
+        Strategy::TwoPointer => k_sum_two_pointer(values, k, target),
+        Strategy::MeetInTheMiddle => k_sum_meet_in_the_middle(values, k, target),
+    };
+    for tuple in found.iter_mut() {
+        tuple.sort_unstable();
+    }
+    found.sort_unstable();
+    found
+}
+
+// Calls `f` with every combination of `k` indices from `indices` (in the order given) and its sum.
+fn combinations<F>(values: &[i64], indices: &[usize], k: usize, f: &mut F)
+where
+    F: FnMut(&[usize], i64),
+{
+    fn go<F>(
+        values: &[i64],
+        indices: &[usize],
+        k: usize,
+        start: usize,
+        chosen: &mut Vec<usize>,
+        sum: i64,
+        f: &mut F,
+    ) where
+        F: FnMut(&[usize], i64),
+    {
+        if chosen.len() == k {
+            return f(chosen, sum);
+        }
+        // Leave enough indices for the rest of the combination.
+        for i in start..=indices.len() - (k - chosen.len()) {
+            chosen.push(indices[i]);
+            go(values, indices, k, i + 1, chosen, sum + values[indices[i]], f);
+            chosen.pop();
+        }
+    }
+    if k <= indices.len() {
+        go(values, indices, k, 0, &mut Vec::with_capacity(k), 0, f);
+    }
+}
+
+fn k_sum_hash(values: &[i64], k: usize, target: i64) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return if target == 0 { vec![vec![]] } else { vec![] };
+    }
+    let mut at: HashMap<i64, Vec<usize>> = HashMap::new();
+    for (i, v) in values.iter().enumerate() {
+        at.entry(*v).or_default().push(i);
+    }
+    let all: Vec<usize> = (0..values.len()).collect();
+    let mut found = Vec::new();
+    combinations(values, &all, k - 1, &mut |chosen, sum| {
+        // Only take a last index past the others, so each combination turns up once.
+        let after = chosen.last().map_or(0, |i| i + 1);
+        for last in at.get(&(target - sum)).into_iter().flatten().filter(|i| **i >= after) {
+            found.push(chosen.iter().cloned().chain(Some(*last)).collect());
+        }
+    });
+    found
+}
+
+fn k_sum_two_pointer(values: &[i64], k: usize, target: i64) -> Vec<Vec<usize>> {
+    if k < 2 {
+        return k_sum_hash(values, k, target);
+    }
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by_key(|i| values[*i]);
+    let sorted: Vec<i64> = order.iter().map(|i| values[*i]).collect();
+    let positions: Vec<usize> = (0..order.len()).collect();
+    let mut found = Vec::new();
+    combinations(&sorted, &positions, k - 2, &mut |chosen, sum| {
+        let rest = target - sum;
+        let mut lo = chosen.last().map_or(0, |p| p + 1);
+        let mut hi = sorted.len() - 1;
+        while lo < hi {
+            let pair = sorted[lo] + sorted[hi];
+            if pair < rest {
+                lo += 1;
+            } else if pair > rest {
+                hi -= 1;
+            } else {
+                // Every pairing between the runs of equal values at each end matches.
+                let mut pairs = vec![];
+                if sorted[lo] == sorted[hi] {
+                    for a in lo..hi {
+                        pairs.extend((a + 1..=hi).map(|b| (a, b)));
+                    }
+                    lo = hi;
+                } else {
+                    let lo_end = lo + sorted[lo..].iter().take_while(|v| **v == sorted[lo]).count();
+                    let hi_start = hi + 1 - sorted[..=hi].iter().rev().take_while(|v| **v == sorted[hi]).count();
+                    for a in lo..lo_end {
+                        pairs.extend((hi_start..=hi).map(|b| (a, b)));
+                    }
+                    lo = lo_end;
+                    hi = hi_start - 1;
+                }
+                for (a, b) in pairs {
+                    found.push(chosen.iter().chain(&[a, b]).map(|p| order[*p]).collect());
+                }
+            }
+        }
+    });
+    found
+}
+
+fn k_sum_meet_in_the_middle(values: &[i64], k: usize, target: i64) -> Vec<Vec<usize>> {
+    let all: Vec<usize> = (0..values.len()).collect();
+    // Every combination (in increasing order) is its first k / 2 indices, the head, followed by the
+    // rest, the tail. Pairing each tail only with heads that end before it starts finds it once.
+    let head = k / 2;
+    let mut heads: HashMap<i64, Vec<Vec<usize>>> = HashMap::new();
+    combinations(values, &all, head, &mut |chosen, sum| {
+        heads.entry(sum).or_default().push(chosen.to_vec());
+    });
+    for same_sum in heads.values_mut() {
+        same_sum.sort_unstable_by_key(|h| h.last().cloned());
+    }
+    let mut found = Vec::new();
+    combinations(values, &all, k - head, &mut |tail, sum| {
+        if let Some(same_sum) = heads.get(&(target - sum)) {
+            let before = same_sum.partition_point(|h| match (h.last(), tail.first()) {
+                (Some(end), Some(start)) => end < start,
+                _ => true,
+            });
+            for h in &same_sum[..before] {
+                found.push(h.iter().chain(tail).cloned().collect());
+            }
+        }
+    });
+    found
+}
+
+/// Some set of entries (of any size) that adds up to `target`, as increasing indices.
+///
+/// This tracks every reachable sum in a bitset, from the sum of the negative values up to `target`
+/// less that (or the sum of the positive values, if lower), along with a 4-byte index for each. So
+/// it takes O(n * range / 64) time and O(range) memory, and gives up with None as well once `range`
+/// passes `MAX_SUMS`.
+pub fn subset_sum(values: &[i64], target: i64) -> Option<Vec<usize>> {
+    let table = SumTable::new(values, None, target)?;
+    let s = table.index(target)?;
+    if !table.layers[0].has(s) {
+        return None;
+    }
+    Some(table.trace(values, 0, s))
+}
+
+/// The `k` entries whose sum comes closest to `target` without going over, as that sum and increasing
+/// indices. None if there are fewer than `k` entries, or every `k` of them add up to more.
+///
+/// Like `subset_sum`, but with a bitset for each number of entries up to `k`, so k times the cost,
+/// and `MAX_SUMS` counts every one of them.
+pub fn closest_k_sum(values: &[i64], k: usize, target: i64) -> Option<(i64, Vec<usize>)> {
+    if k > values.len() {
+        return None;
+    }
+    let table = SumTable::new(values, Some(k), target)?;
+    let top = match table.index(target) {
+        Some(s) => s,
+        None if target > table.min => table.layers[k].first.len() - 1,
+        None => return None,
+    };
+    let s = (0..=top).rev().find(|s| table.layers[k].has(*s))?;
+    Some((table.min + s as i64, table.trace(values, k, s)))
+}
+
+// Which sums can be made from some of the values, and how. Sums are stored offset by `min`, the
+// smallest possible one. With a count there's a layer for each number of values used up to it, and
+// without one a single layer for any number.
+struct SumTable {
+    min: i64,
+    counted: bool,
+    layers: Vec<Layer>,
+}
+
+struct Layer {
+    bits: Vec<u64>,
+    // The index of the first value that made each sum reachable, or NONE (also for the empty sum).
+    first: Vec<u32>,
+}
+
+const NONE: u32 = u32::MAX;
+
+/// The most sums `subset_sum` and `closest_k_sum` will track, about 68MB worth.
+pub const MAX_SUMS: usize = 1 << 24;
+
+impl Layer {
+    fn new(len: usize) -> Layer {
+        Layer {
+            bits: vec![0; len.div_ceil(64)],
+            first: vec![NONE; len],
+        }
+    }
+
+    fn has(&self, s: usize) -> bool {
+        self.bits[s / 64] >> (s % 64) & 1 == 1
+    }
+}
+
+impl SumTable {
+    // None if the sums that could lead to `target` won't fit in `MAX_SUMS`, or overflow.
+    fn new(values: &[i64], count: Option<usize>, target: i64) -> Option<SumTable> {
+        let total = |keep: fn(&i64) -> bool| {
+            values.iter().filter(|v| keep(v)).try_fold(0i64, |sum, v| sum.checked_add(*v))
+        };
+        let min = total(|v| *v < 0)?;
+        let max = total(|v| *v > 0)?;
+        // Nothing past `target - min` can come back down to `target`, but the empty sum has to fit.
+        let top = target.checked_sub(min).map_or(max, |t| t.min(max)).max(0);
+        let len = top.checked_sub(min)?.checked_add(1)?;
+        let layer_count = count.unwrap_or(0) + 1;
+        if len > (MAX_SUMS / layer_count) as i64 || values.len() >= NONE as usize {
+            return None;
+        }
+        let len = len as usize;
+        let mut layers: Vec<Layer> = (0..layer_count).map(|_| Layer::new(len)).collect();
+        let zero = (-min) as usize;
+        layers[0].bits[zero / 64] |= 1 << (zero % 64);
+        for (i, v) in values.iter().enumerate() {
+            match count {
+                // Higher counts first, so each only builds on sums made without this value.
+                Some(k) => {
+                    for c in (1..=k.min(i + 1)).rev() {
+                        let shifted = shift(&layers[c - 1].bits, *v, len);
+                        add(&mut layers[c], &shifted, i);
+                    }
+                }
+                None => {
+                    let shifted = shift(&layers[0].bits, *v, len);
+                    add(&mut layers[0], &shifted, i);
+                }
+            }
+        }
+        Some(SumTable {
+            min,
+            counted: count.is_some(),
+            layers,
+        })
+    }
+
+    fn index(&self, sum: i64) -> Option<usize> {
+        let s = sum.checked_sub(self.min)?;
+        if s >= 0 && (s as usize) < self.layers[0].first.len() {
+            Some(s as usize)
+        } else {
+            None
+        }
+    }
+
+    // The values that make up the reachable sum `s` in `layer`, in increasing order. Each step back
+    // goes to a sum that was reachable before the value that was just taken off, so to an earlier one.
+    fn trace(&self, values: &[i64], mut layer: usize, mut s: usize) -> Vec<usize> {
+        let mut indices = Vec::new();
+        loop {
+            let i = self.layers[layer].first[s];
+            if i == NONE || (self.counted && layer == 0) {
+                break;
+            }
+            indices.push(i as usize);
+            s = (s as i64 - values[i as usize]) as usize;
+            if self.counted {
+                layer -= 1;
+            }
+        }
+        indices.reverse();
+        indices
+    }
+}
+
+// `bits` moved up by `by` places (down if negative), keeping the first `len`.
+fn shift(bits: &[u64], by: i64, len: usize) -> Vec<u64> {
+    let word = |i: i64| {
+        if i >= 0 && (i as usize) < bits.len() {
+            bits[i as usize]
+        } else {
+            0
+        }
+    };
+    let (words, rem) = (by.div_euclid(64), by.rem_euclid(64) as u32);
+    let mut shifted: Vec<u64> = (0..bits.len() as i64)
+        .map(|w| {
+            let (hi, lo) = (word(w - words), word(w - words - 1));
+            if rem == 0 {
+                hi
+            } else {
+                hi << rem | lo >> (64 - rem)
+            }
+        })
+        .collect();
+    if !len.is_multiple_of(64) {
+        *shifted.last_mut().unwrap() &= (1 << (len % 64)) - 1;
+    }
+    shifted
+}
+
+// Mark the sums in `shifted` as reachable, noting value `i` as the way to any that weren't already.
+fn add(layer: &mut Layer, shifted: &[u64], i: usize) {
+    for (w, bits) in shifted.iter().enumerate() {
+        let mut new = bits & !layer.bits[w];
+        layer.bits[w] |= new;
+        while new != 0 {
+            layer.first[w * 64 + new.trailing_zeros() as usize] = i as u32;
+            new &= new - 1;
+        }
+    }
+}
+
+fn product(values: &[i32], indices: &[usize]) -> i64 {
+    indices.iter().map(|i| values[*i] as i64).product()
+}
+
+#[aoc(day1, part1, k_sum)]
+pub fn solve_part1_k_sum(input: &str) -> Result<i64, ParseError> {
+    solve_k_sum(input, 2)
+}
+
+#[aoc(day1, part2, k_sum)]
+pub fn solve_part2_k_sum(input: &str) -> Result<i64, ParseError> {
+    solve_k_sum(input, 3)
+}
+
+#[aoc(day1, part1, bitset)]
+pub fn solve_part1_bitset(input: &str) -> Result<i64, ParseError> {
+    solve_closest_k_sum(input, 2)
+}
+
+#[aoc(day1, part2, bitset)]
+pub fn solve_part2_bitset(input: &str) -> Result<i64, ParseError> {
+    solve_closest_k_sum(input, 3)
+}
+
+fn solve_closest_k_sum(input: &str, k: usize) -> Result<i64, ParseError> {
+    let src = Source::new(1, input);
+    let values: Vec<i32> = src.numbers().collect::<Result<_, _>>()?;
+    let wide: Vec<i64> = values.iter().map(|v| *v as i64).collect();
+    match closest_k_sum(&wide, k, 2020) {
+        Some((2020, indices)) => Ok(product(&values, &indices)),
+        None if k <= wide.len() && SumTable::new(&wide, Some(k), 2020).is_none() => {
+            Err(src.missing(format!("expected entries whose sums span at most {} values", MAX_SUMS)))
+        }
+        _ => Err(no_sum(&src, k)),
+    }
+}
+
+fn solve_k_sum(input: &str, k: usize) -> Result<i64, ParseError> {
+    let src = Source::new(1, input);
+    let values: Vec<i32> = src.numbers().collect::<Result<_, _>>()?;
+    let wide: Vec<i64> = values.iter().map(|v| *v as i64).collect();
+    match find_k_sum(&wide, k, 2020).first() {
+        Some(indices) => Ok(product(&values, indices)),
+        None => Err(no_sum(&src, k)),
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error::{ParseError, Source};
+use itertools::sorted;
+use std::iter::once;
+
+// The differences between successive joltages in the chain from the outlet to the device, each of
+// which must be 1 to 3.
+fn joltage_differences(input: &str) -> Result<Vec<i32>, ParseError> {
+    let src = Source::new(10, input);
+    let adapters: Vec<i32> = src.numbers().collect::<Result<_, _>>()?;
+    let chain: Vec<i32> = sorted(
+        adapters
+            .into_iter()
+            // Implicit outlet has 0 jolt rating
+            .chain(once(0)),
+    )
+    .collect();
+    if let Some(w) = chain.windows(2).find(|w| !(1..=3).contains(&(w[1] - w[0]))) {
+        let message = format!("expected an adapter 1 to 3 jolts above {}", w[0]);
+        return Err(match input.lines().find(|l| l.trim().parse() == Ok(w[1])) {
+            Some(line) => src.error(line, message),
+            None => src.missing(message),
+        });
+    }
+    Ok(chain
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        // Implicit device's built-in adapter is +3
+        .chain(once(3))
+        .collect())
+}
+
+#[aoc(day10, part1)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    Ok(joltage_differences(input)?
+        .into_iter()
+        // Accumulate a tuple of [count of diff 1, count of diff 3]
+        .fold([0; 2], |acc, d| match d {
+            1 => [acc[0] + 1, acc[1]],
+            2 => acc,
+            // Only 3 is left.
+            _ => [acc[0], acc[1] + 1],
+        })
+        .iter()
+        // Take the product of (count of diff 1) x (count of diff 2)
+        .product())
+}
+
+#[aoc(day10, part2)]
+pub fn solve_part2(input: &str) -> Result<i64, ParseError> {
+    Ok(joltage_differences(input)?
+        .into_iter()
+        // acc is a tuple of running counts where acc[x] is the number of ways
+        // one can get to the current delta `d` with an active group having sum `x`.
+        .fold([1, 0, 0], |acc, d| match d {
+            1 => [acc[2], acc.iter().sum(), acc[1]],
+            2 => [acc[1], 0, acc.iter().sum()],
+            // Only 3 is left.
+            _ => [acc.iter().sum(), 0, 0],
+        })
+        .iter()
+        .sum())
+}
@@ -0,0 +1,50 @@
+use crate::automaton::{Automaton, Dense, Square};
+use crate::error::{ParseError, Source};
+use crate::grid::{Grid, Pos, DIRS8};
+
+const OCCUPIED: char = '#';
+const EMPTY: char = 'L';
+const FLOOR: char = '.';
+
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    let src = Source::new(11, input);
+    Grid::parse(&src, input, "one of `#`, `L` or `.`", |c| match c {
+        OCCUPIED | EMPTY | FLOOR => Some(c),
+        _ => None,
+    })
+}
+
+// Only seats take part; an occupied seat is a live cell.
+fn seats(layout: &Grid<char>) -> impl Iterator<Item = (Pos, bool)> + '_ {
+    layout.iter().filter(|(_, c)| **c != FLOOR).map(|(pos, c)| (pos, *c == OCCUPIED))
+}
+
+// Empty seats with no occupied neighbors fill up, and occupied seats with `tolerance` or more empty out.
+fn rule(tolerance: usize) -> impl Fn(bool, usize) -> bool {
+    move |occupied, n| if occupied { n < tolerance } else { n == 0 }
+}
+
+#[aoc(day11, part1)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    let layout = parse(input)?;
+    let topology = Square::moore(layout.width(), layout.height());
+    let mut automaton = Dense::new(&topology, rule(4), seats(&layout));
+    automaton.settle();
+    Ok(automaton.population() as i32)
+}
+
+
+#[aoc(day11, part2)]
+pub fn solve_part2(input: &str) -> Result<i32, ParseError> {
+    let layout = parse(input)?;
+    // The first seat in each direction, looking past the floor.
+    let visible = |pos: &Pos| -> Vec<Pos> {
+        DIRS8
+            .iter()
+            .filter_map(|dir| layout.ray(*pos, *dir).find(|p| layout[*p] != FLOOR))
+            .collect()
+    };
+    let mut automaton = Dense::new(&visible, rule(5), seats(&layout));
+    automaton.settle();
+    Ok(automaton.population() as i32)
+}
@@ -1,3 +1,4 @@
+use crate::error::{ParseError, Source};
 use regex::Regex;
 use lazy_static::lazy_static;
 use num_complex::Complex;
@@ -19,16 +20,33 @@ const E: Complex<i32> = Complex::new(1, 0);
 const W: Complex<i32> = Complex::new(-1, 0);
 
 
-#[aoc(day12, part1)]
-pub fn solve_part1(input: &str) -> i32 {
+fn parse(input: &str) -> Result<Vec<(&str, i32)>, ParseError> {
     lazy_static! {
         // Named capture groups were 20% slower, but oh well. 
-        static ref LINE_RE: Regex = Regex::new(r"(?P<act>[NSEWLRF])(?P<val>\d+)$").unwrap();
+        static ref LINE_RE: Regex = Regex::new(r"^(?P<act>[NSEWLRF])(?P<val>\d+)$").unwrap();
     }
-    input.lines().fold((O, E), |(pos, dir), l| {
-        let caps = LINE_RE.captures(l).unwrap();
-        let val = caps.name("val").unwrap().as_str().parse::<i32>().unwrap();
-        match caps.name("act").unwrap().as_str() {
+    let src = Source::new(12, input);
+    input
+        .lines()
+        .map(|l| {
+            let caps = src.captures(&LINE_RE, l, "an action in NSEWLRF followed by a number")?;
+            let act = caps.name("act").unwrap().as_str();
+            let val = src.parse::<i32>(caps.name("val").unwrap().as_str())?;
+            // Turns are applied as whole quarter rotations.
+            if (act == "L" || act == "R") && val % 90 != 0 {
+                return Err(src.error(l, "rotations must be a multiple of 90 degrees"));
+            }
+            Ok((act, val))
+        })
+        .collect()
+}
+
+
+#[aoc(day12, part1)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    let actions = parse(input)?;
+    Ok(actions.into_iter().fold((O, E), |(pos, dir), (act, val)| {
+        match act {
             "N" => (pos + N * val, dir),
             "S" => (pos + S * val, dir),
             "E" => (pos + E * val, dir),
@@ -38,20 +56,15 @@ pub fn solve_part1(input: &str) -> i32 {
             "F" => (pos + dir * val, dir),
             _ => panic!(),
         }
-    }).0.l1_norm()
+    }).0.l1_norm())
 }
 
 
 #[aoc(day12, part2)]
-pub fn solve_part2(input: &str) -> i32 {
-    lazy_static! {
-        // Named capture groups were 20% slower, but oh well. 
-        static ref LINE_RE: Regex = Regex::new(r"(?P<act>[NSEWLRF])(?P<val>\d+)$").unwrap();
-    }
-    input.lines().fold((O, 10 * E + 1 * N), |(pos, off), l| {
-        let caps = LINE_RE.captures(l).unwrap();
-        let val = caps.name("val").unwrap().as_str().parse::<i32>().unwrap();
-        match caps.name("act").unwrap().as_str() {
+pub fn solve_part2(input: &str) -> Result<i32, ParseError> {
+    let actions = parse(input)?;
+    Ok(actions.into_iter().fold((O, 10 * E + 1 * N), |(pos, off), (act, val)| {
+        match act {
             "N" => (pos, off + N * val),
             "S" => (pos, off + S * val),
             "E" => (pos, off + E * val),
@@ -61,5 +74,5 @@ pub fn solve_part2(input: &str) -> i32 {
             "F" => (pos + off * val, off),
             _ => panic!(),
         }
-    }).0.l1_norm()
+    }).0.l1_norm())
 }
@@ -1,24 +1,35 @@
-#[aoc(day13, part1)]
-pub fn solve_part1(input: &str) -> i32 {
+use crate::error::{ParseError, Source};
+
+// Returns the earliest timestamp, and each bus id with its offset in the schedule.
+pub fn parse_notes(input: &str) -> Result<(i64, Vec<(usize, i64)>), ParseError> {
+    let src = Source::new(13, input);
     let mut iter = input.lines();
-    let earliest = iter.next().unwrap().parse::<i32>().unwrap();
-    // let buses: Vec<i32> = iter.next().unwrap().split(',').filter(|b| *b != "x").map(|b| b.parse::<i32>().unwrap()).collect();
-    let buses: Vec<i32> = iter
+    let earliest = src.parse::<i64>(iter.next().ok_or_else(|| src.missing("expected the earliest timestamp"))?)?;
+    let bus_data = iter
         .next()
-        .unwrap()
+        .ok_or_else(|| src.missing("expected a comma-separated bus schedule"))?
         .split(',')
-        .filter_map(|b| match b.parse::<i32>() {
-            Ok(bus_id) => Some(bus_id),
-            Err(_) => None,
+        .enumerate()
+        .filter(|(_, b)| *b != "x")
+        .map(|(i, b)| match src.parse::<i64>(b)? {
+            bus_id if bus_id > 0 => Ok((i, bus_id)),
+            _ => Err(src.error(b, "bus ids must be positive")),
         })
-        .collect();
+        .collect::<Result<Vec<(usize, i64)>, ParseError>>()?;
+    Ok((earliest, bus_data))
+}
+
+#[aoc(day13, part1)]
+pub fn solve_part1(input: &str) -> Result<i64, ParseError> {
+    let (earliest, bus_data) = parse_notes(input)?;
+    let src = Source::new(13, input);
     // Technically the `1` below should be conditional based on if earliest % b == 0.
-    let div: (i32, i32) = buses
+    let div: (i64, i64) = bus_data
         .iter()
-        .map(|b| ((earliest / b + 1) * b, *b))
+        .map(|(_, b)| ((earliest / b + 1) * b, *b))
         .min()
-        .unwrap();
-    div.1 * (div.0 - earliest)
+        .ok_or_else(|| src.missing("expected at least one bus in service"))?;
+    Ok(div.1 * (div.0 - earliest))
 }
 
 // Successive remainders from each iteration of the extended euclidean algorithm.
@@ -91,8 +102,8 @@ pub fn mult(a: i64, b: i64, m: i64) -> i64 {
 }
 
 pub struct ChineseRemainder {
-    r: i64, // remainder
-    m: i64, // modulus
+    pub r: i64, // remainder
+    pub m: i64, // modulus
 }
 
 /// Solve the congruence {R = crs[i].r (modulo crs[i].m) for all i}.
@@ -129,36 +140,18 @@ pub fn solve_chinese_remainder_theorem_brute<I: IntoIterator<Item = ChineseRemai
 
 
 #[aoc(day13, part2)]
-pub fn solve_part2(input: &str) -> i64 {
-    let mut iter = input.lines();
-    iter.next(); // ignore your "earliest timestamp" for this part.
-    let bus_data: Vec<(usize, i64)> = iter
-        .next()
-        .unwrap()
-        .split(',')
-        .enumerate()
-        .filter(|(_, b)| *b != "x")
-        .map(|(i, b)| (i, b.parse::<i64>().unwrap()))
-        .collect();
-    solve_chinese_remainder_theorem(
+pub fn solve_part2(input: &str) -> Result<i64, ParseError> {
+    let (_, bus_data) = parse_notes(input)?; // ignore your "earliest timestamp" for this part.
+    Ok(solve_chinese_remainder_theorem(
         bus_data.iter().map(|(i, b)| ChineseRemainder {r: (b - (*i as i64)).rem_euclid(*b), m: *b})
-    ).r
+    ).r)
 }
 
 // NB: This is 15% faster than the more complex number theory solution.
 #[aoc(day13, part2, brute)]
-pub fn solve_part2_brute(input: &str) -> i64 {
-    let mut iter = input.lines();
-    iter.next(); // ignore your "earliest timestamp" for this part.
-    let bus_data: Vec<(usize, i64)> = iter
-        .next()
-        .unwrap()
-        .split(',')
-        .enumerate()
-        .filter(|(_, b)| *b != "x")
-        .map(|(i, b)| (i, b.parse::<i64>().unwrap()))
-        .collect();
-    solve_chinese_remainder_theorem_brute(
+pub fn solve_part2_brute(input: &str) -> Result<i64, ParseError> {
+    let (_, bus_data) = parse_notes(input)?; // ignore your "earliest timestamp" for this part.
+    Ok(solve_chinese_remainder_theorem_brute(
         bus_data.iter().map(|(i, b)| ChineseRemainder {r: (b - (*i as i64)).rem_euclid(*b), m: *b})
-    ).r
+    ).r)
 }
@@ -1,3 +1,4 @@
+use crate::error::{ParseError, Source};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
@@ -94,37 +95,39 @@ impl Program {
         mem_map
     }
 
-    pub fn parse(source: &str) -> Program {
+    pub fn parse(source: &str) -> Result<Program, ParseError> {
         lazy_static! {
             static ref INSTR_RE: Regex =
-                Regex::new(r"(mem\[(\d+)] = (\d+))|(mask = ([01X]+))").unwrap();
+                Regex::new(r"^(?:(mem\[(\d+)] = (\d+))|(mask = ([01X]{36})))$").unwrap();
         }
-        Program {
-            instructions: INSTR_RE
-                .captures_iter(source)
-                .map(|caps| {
+        let src = Source::new(14, source);
+        Ok(Program {
+            instructions: source
+                .lines()
+                .map(|line| {
+                    let caps = src.captures(&INSTR_RE, line, "`mem[<addr>] = <value>` or a 36-bit `mask = <mask>`")?;
                     if caps.get(1).is_some() {
-                        Instruction::Mem {
-                            addr: caps[2].parse::<u64>().unwrap(),
-                            value: caps[3].parse::<u64>().unwrap(),
-                        }
+                        Ok(Instruction::Mem {
+                            addr: src.parse::<u64>(caps.get(2).unwrap().as_str())?,
+                            value: src.parse::<u64>(caps.get(3).unwrap().as_str())?,
+                        })
                     } else {
-                        Instruction::Mask(caps[5].to_string())
+                        Ok(Instruction::Mask(caps[5].to_string()))
                     }
                 })
-                .collect(),
-        }
+                .collect::<Result<Vec<Instruction>, ParseError>>()?,
+        })
     }
 }
 
 #[aoc(day14, part1)]
-pub fn solve_part1(input: &str) -> u64 {
-    let program = Program::parse(input);
-    program.run_v1().values().sum()
+pub fn solve_part1(input: &str) -> Result<u64, ParseError> {
+    let program = Program::parse(input)?;
+    Ok(program.run_v1().values().sum())
 }
 
 #[aoc(day14, part2)]
-pub fn solve_part2(input: &str) -> u64 {
-    let program = Program::parse(input);
-    program.run_v2().values().sum()
+pub fn solve_part2(input: &str) -> Result<u64, ParseError> {
+    let program = Program::parse(input)?;
+    Ok(program.run_v2().values().sum())
 }
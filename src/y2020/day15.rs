@@ -1,7 +1,12 @@
+use crate::error::{ParseError, Source};
 use std::collections::HashMap;
 
-pub fn count(input: &str, n: usize) -> usize {
-    let seed: Vec<usize> = input.split(',').map(|x| x.parse::<usize>().unwrap()).collect();
+pub fn parse_seed(input: &str) -> Result<Vec<usize>, ParseError> {
+    let src = Source::new(15, input);
+    input.trim_end().split(',').map(|x| src.parse::<usize>(x)).collect()
+}
+
+pub fn count(seed: &[usize], n: usize) -> usize {
     let mut map: HashMap::<usize, usize> = HashMap::new();
     let mut last_num: usize = 0;
     let mut next_num: usize = 0;
@@ -20,12 +25,12 @@ pub fn count(input: &str, n: usize) -> usize {
 }
 // 150us
 #[aoc(day15, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    count(input, 2020)
+pub fn solve_part1(input: &str) -> Result<usize, ParseError> {
+    Ok(count(&parse_seed(input)?, 2020))
 }
 
 // 3s :grimacing:
 #[aoc(day15, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    count(input, 30000000)
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    Ok(count(&parse_seed(input)?, 30000000))
 }
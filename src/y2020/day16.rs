@@ -0,0 +1,599 @@
+use crate::error::{ParseError, Source};
+use crate::interval::IntervalSet;
+use crate::matching::{self, Assignment, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+lazy_static! {
+    static ref INPUT_RE: Regex =
+        Regex::new(r"(?s)(.+)\n\nyour ticket:\n(.+)\n\nnearby tickets:\n(.+)").unwrap();
+}
+
+
+/// A field, and the values it accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketRange {
+    pub name: String,
+    pub values: IntervalSet,
+}
+
+pub struct TicketData {
+    pub ranges: Vec<TicketRange>,
+    pub my_ticket: Vec<i32>,
+    // The valid nearby tickets, each with its position among all of them (from 0).
+    pub other_tickets: Vec<(usize, Vec<i32>)>,
+}
+
+/// Why the fields can't be matched up with columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unassignable {
+    /// More than one assignment fits. Each field that could be in several columns, with those columns.
+    Ambiguous(Vec<(String, Vec<usize>)>),
+    /// No assignment fits, since these fields only fit `columns` between them, and there are fewer
+    /// of those. `evidence` is a minimal set of ticket values that rule out every other column.
+    Unsatisfiable {
+        fields: Vec<String>,
+        columns: Vec<usize>,
+        evidence: Vec<Exclusion>,
+    },
+}
+
+/// A value on a valid nearby ticket that no field in `fields` accepts, so none of them is in `column`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exclusion {
+    pub ticket: usize, // Position among the nearby tickets, from 0.
+    pub column: usize,
+    pub value: i32,
+    pub fields: Vec<String>,
+}
+
+/// How each nearby ticket fares against the rules, for auditing a batch of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    pub tickets: Vec<TicketReport>,
+}
+
+/// A ticket is valid if every one of its values fits at least one field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TicketReport {
+    pub ticket: usize, // Position among the nearby tickets, from 0.
+    pub valid: bool,
+    /// The columns holding values that fit no field.
+    pub invalid_columns: Vec<usize>,
+    pub values: Vec<ValueReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValueReport {
+    pub column: usize,
+    pub value: i32,
+    /// The fields that accept this value, in the order of their rules.
+    pub fields: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn new(ranges: &[TicketRange], tickets: &[Vec<i32>]) -> ValidationReport {
+        let tickets = tickets
+            .iter()
+            .enumerate()
+            .map(|(ticket, values)| {
+                let values: Vec<ValueReport> = values
+                    .iter()
+                    .enumerate()
+                    .map(|(column, v)| ValueReport {
+                        column,
+                        value: *v,
+                        fields: ranges
+                            .iter()
+                            .filter(|r| TicketData::is_a_valid_ticket_value(*v, r))
+                            .map(|r| r.name.clone())
+                            .collect(),
+                    })
+                    .collect();
+                let invalid_columns: Vec<usize> =
+                    values.iter().filter(|v| v.fields.is_empty()).map(|v| v.column).collect();
+                TicketReport {
+                    ticket,
+                    valid: invalid_columns.is_empty(),
+                    invalid_columns,
+                    values,
+                }
+            })
+            .collect();
+        ValidationReport { tickets }
+    }
+
+    /// The report for the nearby tickets in a puzzle input.
+    pub fn parse(input: &str) -> Result<ValidationReport, ParseError> {
+        let src = Source::new(16, input);
+        let (ranges_str, _, other_tickets_str) = TicketData::split_sections(&src)?;
+        let ranges = TicketData::parse_ranges(&src, ranges_str)?;
+        let tickets = TicketData::parse_tickets(&src, other_tickets_str)?;
+        Ok(ValidationReport::new(&ranges, &tickets))
+    }
+
+    /// The sum of every value that fits no field (part 1's answer).
+    pub fn error_rate(&self) -> i32 {
+        self.tickets
+            .iter()
+            .flat_map(|t| t.invalid_columns.iter().map(move |c| t.values[*c].value))
+            .sum()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// One row per value, with the fields it fits separated by `;`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("ticket,valid,column,value,fields\n");
+        for t in self.tickets.iter() {
+            for v in t.values.iter() {
+                csv += &format!(
+                    "{},{},{},{},{}\n",
+                    t.ticket,
+                    t.valid,
+                    v.column,
+                    v.value,
+                    csv_field(&v.fields.join(";"))
+                );
+            }
+        }
+        csv
+    }
+}
+
+// Field names can hold commas and quotes, so quote them when they do.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+impl fmt::Display for Unassignable {
+    // ambiguous: `class` could be in column 0 or 1; `row` could be in column 0 or 1
+    // unsatisfiable: `a` and `b` only fit column 2; ticket 3 has 12 in column 0 (rules out `a`, `b`), ...
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = |fields: &[String]| {
+            fields.iter().map(|n| format!("`{}`", n)).collect::<Vec<String>>().join(", ")
+        };
+        let columns = |columns: &[usize]| {
+            columns.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(" or ")
+        };
+        match self {
+            Unassignable::Ambiguous(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, cs)| format!("`{}` could be in column {}", name, columns(cs)))
+                    .collect();
+                write!(f, "ambiguous: {}", fields.join("; "))
+            }
+            Unassignable::Unsatisfiable { fields, columns: cs, evidence } => {
+                let evidence: Vec<String> = evidence
+                    .iter()
+                    .map(|e| {
+                        let out = names(&e.fields);
+                        format!("ticket {} has {} in column {} (rules out {})", e.ticket, e.value, e.column, out)
+                    })
+                    .collect();
+                let fields = names(fields);
+                write!(f, "unsatisfiable: {} only fit column {}; {}", fields, columns(cs), evidence.join(", "))
+            }
+        }
+    }
+}
+
+// A nearby ticket, with the fields its value in some column rules out of that column.
+type RulingOut<'a> = (&'a (usize, Vec<i32>), HashSet<usize>);
+
+impl TicketData {
+    pub fn parse(input: &str) -> Result<TicketData, ParseError> {
+        let src = Source::new(16, input);
+        let (ranges_str, my_ticket_str, other_tickets_str) = TicketData::split_sections(&src)?;
+        let ranges: Vec<TicketRange> = TicketData::parse_ranges(&src, ranges_str)?;
+        let my_ticket: Vec<i32> = TicketData::parse_valid_tickets(&src, my_ticket_str, &ranges)?
+            .first()
+            .ok_or_else(|| src.error(my_ticket_str, "your ticket has a value that matches no field"))?
+            .1
+            .to_vec();
+        let other_tickets = TicketData::parse_valid_tickets(&src, other_tickets_str, &ranges)?;
+        Ok(TicketData {
+            ranges,
+            my_ticket,
+            other_tickets,
+        })
+    }
+
+    // Returns the (field rules, your ticket, nearby tickets) sections.
+    pub fn split_sections<'a>(src: &Source<'a>) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+        let caps = INPUT_RE.captures(src.input).ok_or_else(|| {
+            src.missing("expected field rules, then `your ticket:` and `nearby tickets:` sections")
+        })?;
+        Ok((
+            caps.get(1).unwrap().as_str(),
+            caps.get(2).unwrap().as_str(),
+            caps.get(3).unwrap().as_str(),
+        ))
+    }
+
+    pub fn parse_tickets(src: &Source, input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+        input
+            .lines()
+            .map(|r| {
+                r.split(',')
+                    .map(|s| src.parse::<i32>(s))
+                    .collect::<Result<Vec<i32>, ParseError>>()
+            })
+            .collect()
+    }
+
+    pub fn parse_valid_tickets(
+        src: &Source,
+        input: &str,
+        ranges: &[TicketRange],
+    ) -> Result<Vec<(usize, Vec<i32>)>, ParseError> {
+        let tickets = TicketData::parse_tickets(src, input)?;
+        let width = ranges.len();
+        if let Some((line, _)) = input.lines().zip(tickets.iter()).find(|(_, t)| t.len() != width) {
+            return Err(src.error(line, format!("expected a value for each of the {} fields", width)));
+        }
+        let report = ValidationReport::new(ranges, &tickets);
+        Ok(tickets
+            .into_iter()
+            .zip(report.tickets.iter())
+            .filter(|(_, r)| r.valid)
+            .map(|(t, r)| (r.ticket, t))
+            .collect())
+    }
+
+    // Rules are `<field>: <values>`, e.g. `seat: 1-3 or 5-7`. See `RuleParser` for what can go in
+    // `<values>`.
+    pub fn parse_ranges(src: &Source, input: &str) -> Result<Vec<TicketRange>, ParseError> {
+        lazy_static! {
+            static ref RANGE_RE: Regex = Regex::new(r"^([^:]+): (.*)$").unwrap();
+        }
+        input
+            .lines()
+            .map(|line| {
+                let rcaps = src.captures(&RANGE_RE, line, "`<field>: <values>`")?;
+                Ok(TicketRange {
+                    name: rcaps[1].to_string(),
+                    values: RuleParser::parse(src, rcaps.get(2).unwrap().as_str())?,
+                })
+            })
+            .collect()
+    }
+
+    pub fn is_a_valid_ticket_value(v: i32, range: &TicketRange) -> bool {
+        range.values.contains(v as i64)
+    }
+    
+    pub fn is_column_valid_for_field_range(
+        tickets: &[(usize, Vec<i32>)],
+        range: &TicketRange,
+        col_idx: usize,
+    ) -> bool {
+        tickets.iter().all(|(_, ticket)| TicketData::is_a_valid_ticket_value(ticket[col_idx], range))
+    }
+
+    pub fn to_constraints_map(&self) -> HashMap<usize, HashSet<usize>> {
+        self.ranges
+            .iter()
+            .enumerate()
+            .map(|(j, r)| {
+                (
+                    j,
+                    (0..self.ranges.len())
+                        .filter(|i| TicketData::is_column_valid_for_field_range(&self.other_tickets, r, *i))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Which column each field is in, or why that can't be told, given how `solution` went.
+    pub fn assign_fields(
+        &self,
+        solution: Solution<usize, usize>,
+    ) -> Result<Assignment<usize, usize>, Unassignable> {
+        let name = |i: &usize| self.ranges[*i].name.clone();
+        match solution {
+            Solution::Unique(sol) => Ok(sol),
+            Solution::Multiple(_, _) => Err(Unassignable::Ambiguous(
+                matching::possible_values(&self.to_constraints_map())
+                    .iter()
+                    .filter(|(_, cols)| cols.len() > 1)
+                    .map(|(field, cols)| (name(field), cols.iter().cloned().collect()))
+                    .collect(),
+            )),
+            Solution::Unsatisfiable(conflict) => Err(Unassignable::Unsatisfiable {
+                fields: conflict.keys.iter().map(name).collect(),
+                evidence: self.exclusions(&conflict.keys, &conflict.values),
+                columns: conflict.values,
+            }),
+        }
+    }
+
+    // A minimal set of ticket values that between them keep every one of `fields` out of every
+    // column but `columns`. Each column is covered greedily, then anything redundant is dropped.
+    fn exclusions(&self, fields: &[usize], columns: &[usize]) -> Vec<Exclusion> {
+        let mut evidence = Vec::new();
+        for column in (0..self.ranges.len()).filter(|c| !columns.contains(c)) {
+            // The fields each ticket rules out of this column.
+            let rules_out = |ticket: &[i32]| -> HashSet<usize> {
+                fields
+                    .iter()
+                    .filter(|f| !TicketData::is_a_valid_ticket_value(ticket[column], &self.ranges[**f]))
+                    .cloned()
+                    .collect()
+            };
+            let mut chosen: Vec<RulingOut> = Vec::new();
+            let mut uncovered: HashSet<usize> = fields.iter().cloned().collect();
+            while !uncovered.is_empty() {
+                let best = self
+                    .other_tickets
+                    .iter()
+                    .map(|t| (t, rules_out(&t.1)))
+                    .max_by_key(|(_, out)| out.intersection(&uncovered).count())
+                    .filter(|(_, out)| !out.is_disjoint(&uncovered))
+                    .expect("every field outside `columns` is ruled out by some ticket");
+                uncovered = uncovered.difference(&best.1).cloned().collect();
+                chosen.push(best);
+            }
+            // Greedy choices can be made redundant by later ones.
+            let mut i = 0;
+            while i < chosen.len() {
+                let others: HashSet<usize> = chosen
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .flat_map(|(_, (_, out))| out.iter().cloned())
+                    .collect();
+                if fields.iter().all(|f| others.contains(f)) {
+                    chosen.remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+            evidence.extend(chosen.into_iter().map(|((ticket, values), out)| {
+                let mut out: Vec<usize> = out.into_iter().collect();
+                out.sort_unstable();
+                Exclusion {
+                    ticket: *ticket,
+                    column,
+                    value: values[column],
+                    fields: out.iter().map(|f| self.ranges[*f].name.clone()).collect(),
+                }
+            }));
+        }
+        evidence.sort_by_key(|e| (e.ticket, e.column));
+        evidence
+    }
+}
+
+// The values a field accepts: ranges (`1-3`), single values (`5`) and open bounds (`>= 7`, `< 2`),
+// combined with `or`, `and`, `not` and parentheses. `not` binds tightest and `or` loosest, so
+// `not 1-3 and < 10 or 20` is `((not 1-3) and < 10) or 20`.
+struct RuleParser<'a> {
+    src: &'a Source<'a>,
+    text: &'a str,
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> RuleParser<'a> {
+    fn parse(src: &'a Source<'a>, text: &'a str) -> Result<IntervalSet, ParseError> {
+        let mut parser = RuleParser {
+            src,
+            text,
+            tokens: RuleParser::tokenize(src, text)?,
+            pos: 0,
+        };
+        let values = parser.or_expr()?;
+        match parser.peek() {
+            Some(token) => Err(src.error(token, "expected `or` or `and`")),
+            None => Ok(values),
+        }
+    }
+
+    fn tokenize(src: &Source, text: &'a str) -> Result<Vec<&'a str>, ParseError> {
+        let mut tokens = Vec::new();
+        let mut chars = text.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let mut end = i + c.len_utf8();
+            let mut extend = |f: fn(char) -> bool| {
+                while let Some((j, d)) = chars.peek().cloned().filter(|(_, d)| f(*d)) {
+                    end = j + d.len_utf8();
+                    chars.next();
+                }
+            };
+            match c {
+                c if c.is_whitespace() => continue,
+                c if c.is_ascii_digit() => extend(|d| d.is_ascii_digit()),
+                c if c.is_alphabetic() => extend(char::is_alphabetic),
+                '<' | '>' => extend(|d| d == '='),
+                '-' | '(' | ')' => {}
+                _ => return Err(src.error(&text[i..end], format!("unexpected `{}`", c))),
+            }
+            tokens.push(&text[i..end]);
+        }
+        Ok(tokens)
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).cloned()
+    }
+
+    fn next(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        match self.peek() {
+            Some(token) => {
+                self.pos += 1;
+                Ok(token)
+            }
+            None => Err(self.src.error(&self.text[self.text.len()..], format!("expected {}", expected))),
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or_expr(&mut self) -> Result<IntervalSet, ParseError> {
+        let mut values = self.and_expr()?;
+        while self.eat("or") {
+            values = values.union(&self.and_expr()?);
+        }
+        Ok(values)
+    }
+
+    fn and_expr(&mut self) -> Result<IntervalSet, ParseError> {
+        let mut values = self.unary()?;
+        while self.eat("and") {
+            values = values.intersection(&self.unary()?);
+        }
+        Ok(values)
+    }
+
+    fn unary(&mut self) -> Result<IntervalSet, ParseError> {
+        if self.eat("not") {
+            return Ok(self.unary()?.complement());
+        }
+        if self.eat("(") {
+            let values = self.or_expr()?;
+            let close = self.next("`)`")?;
+            if close != ")" {
+                return Err(self.src.error(close, "expected `)`"));
+            }
+            return Ok(values);
+        }
+        let token = self.next("a value")?;
+        let bound = |parser: &mut RuleParser| parser.number("a number");
+        Ok(match token {
+            ">=" => IntervalSet::at_least(bound(self)?),
+            ">" => bound(self)?.checked_add(1).map_or_else(IntervalSet::empty, IntervalSet::at_least),
+            "<=" => IntervalSet::at_most(bound(self)?),
+            "<" => bound(self)?.checked_sub(1).map_or_else(IntervalSet::empty, IntervalSet::at_most),
+            _ => {
+                self.pos -= 1;
+                let lo = self.number("a value, range, or one of `>=`, `>`, `<=`, `<`, `not` or `(`")?;
+                if !self.eat("-") {
+                    return Ok(IntervalSet::single(lo));
+                }
+                let hi_token = self.peek();
+                let hi = self.number("the end of the range")?;
+                if hi < lo {
+                    let message = format!("range ends before it starts at {}", lo);
+                    return Err(self.src.error(hi_token.unwrap(), message));
+                }
+                IntervalSet::range(lo, hi)
+            }
+        })
+    }
+
+    fn number(&mut self, expected: &str) -> Result<i64, ParseError> {
+        let token = self.next(expected)?;
+        if !token.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(self.src.error(token, format!("expected {}", expected)));
+        }
+        self.src.parse::<i64>(token)
+    }
+}
+
+// Report why there's no answer as a problem with the input: at the rule for the first ambiguous
+// field, or the first ticket that rules something out.
+fn unassignable_error(src: &Source, report: &Unassignable) -> ParseError {
+    let (rules, _, nearby) = TicketData::split_sections(src).unwrap();
+    let line = match report {
+        Unassignable::Ambiguous(fields) => fields
+            .first()
+            .and_then(|(name, _)| rules.lines().find(|l| l.starts_with(&format!("{}:", name)))),
+        Unassignable::Unsatisfiable { evidence, .. } => {
+            evidence.first().and_then(|e| nearby.lines().nth(e.ticket))
+        }
+    };
+    match line {
+        Some(line) => src.error(line, report.to_string()),
+        None => src.missing(report.to_string()),
+    }
+}
+
+#[aoc(day16, part1)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    Ok(ValidationReport::parse(input)?.error_rate())
+}
+
+pub fn calculate_part2(ranges: &[TicketRange], my_ticket: &[i32], sol: &Assignment<usize, usize>) -> i64 {
+    ranges
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.name.starts_with("departure"))
+        .map(|(i, _)| *my_ticket.get(*sol.get(&i).unwrap()).unwrap() as i64)
+        .product()
+}
+
+#[aoc(day16, part2, no_brute)]
+pub fn solve_part2_no_brute(input: &str) -> Result<i64, ParseError> {
+    let ticket_data = TicketData::parse(input)?;
+    let constraints = ticket_data.to_constraints_map();
+    let sol = ticket_data
+        .assign_fields(matching::solve(&constraints))
+        .map_err(|report| unassignable_error(&Source::new(16, input), &report))?;
+    Ok(calculate_part2(&ticket_data.ranges, &ticket_data.my_ticket, &sol))
+}
+
+// A plain exhaustive search, kept apart from `matching` so the two can check each other. Collects up
+// to 2 assignments, trying the field with the fewest columns left first.
+fn brute_solve(
+    constraints: &HashMap<usize, HashSet<usize>>,
+    sol: &mut Assignment<usize, usize>,
+    used: &mut HashSet<usize>,
+    found: &mut Vec<Assignment<usize, usize>>,
+) {
+    let field = constraints
+        .iter()
+        .filter(|(f, _)| !sol.contains_key(f))
+        .min_by_key(|(f, cols)| (cols.iter().filter(|c| !used.contains(c)).count(), **f));
+    let (field, cols) = match field {
+        Some(field) => field,
+        None => return found.push(sol.clone()),
+    };
+    let mut cols: Vec<usize> = cols.iter().filter(|c| !used.contains(c)).cloned().collect();
+    cols.sort_unstable();
+    for col in cols {
+        sol.insert(*field, col);
+        used.insert(col);
+        brute_solve(constraints, sol, used, found);
+        used.remove(&col);
+        sol.remove(field);
+        if found.len() >= 2 {
+            return;
+        }
+    }
+}
+
+#[aoc(day16, part2, brute)]
+pub fn solve_part2_brute(input: &str) -> Result<i64, ParseError> {
+    let ticket_data = TicketData::parse(input)?;
+    let constraints = ticket_data.to_constraints_map();
+    let mut found = Vec::new();
+    brute_solve(&constraints, &mut Assignment::new(), &mut HashSet::new(), &mut found);
+    let solution = match (found.pop(), found.pop()) {
+        (Some(sol), None) => Solution::Unique(sol),
+        // Leave the explaining to the smarter solver.
+        _ => matching::solve(&constraints),
+    };
+    let sol = ticket_data
+        .assign_fields(solution)
+        .map_err(|report| unassignable_error(&Source::new(16, input), &report))?;
+    Ok(calculate_part2(&ticket_data.ranges, &ticket_data.my_ticket, &sol))
+}
@@ -0,0 +1,52 @@
+use crate::automaton::{Automaton, Lattice, Sparse};
+use crate::error::{ParseError, Source};
+use std::collections::HashSet;
+use std::iter::once;
+use std::iter::repeat;
+
+const ACTIVE: char = '#';
+const INACTIVE: char = '.';
+
+pub fn parse(n: usize, input: &str) -> Result<HashSet<Vec<i32>>, ParseError> {
+    let src = Source::new(17, input);
+    for line in input.lines() {
+        if let Some((j, c)) = line.char_indices().find(|(_, c)| *c != ACTIVE && *c != INACTIVE) {
+            return Err(src.error(&line[j..j + c.len_utf8()], "expected `#` or `.`"));
+        }
+    }
+    Ok(input
+        .lines()
+        .enumerate()
+        .flat_map(|(i, l)| l.chars().enumerate().map(move |(j, c)| ((i, j), c)))
+        .filter(|(_, c)| *c == ACTIVE)
+        .map(|((i, j), _)| {
+            repeat(0)
+                .take(n - 2)
+                .chain(once(i as i32))
+                .chain(once(j as i32))
+                .collect()
+        })
+        .collect())
+}
+
+// An active cube stays active with 2 or 3 active neighbors, and an inactive one with exactly 3
+// becomes active.
+fn rule(active: bool, n: usize) -> bool {
+    n == 3 || active && n == 2
+}
+
+fn run(dims: usize, input: &str) -> Result<usize, ParseError> {
+    let mut automaton = Sparse::new(Lattice::new(dims), rule, parse(dims, input)?);
+    automaton.run(6);
+    Ok(automaton.population())
+}
+
+#[aoc(day17, part1)]
+pub fn solve_part1(input: &str) -> Result<usize, ParseError> {
+    run(3, input)
+}
+
+#[aoc(day17, part2)]
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    run(4, input)
+}
@@ -1,12 +1,65 @@
+use crate::error::{ParseError, Source};
 use  std::str::Chars;
+use tracing::{debug_span, trace};
+
+// Both evaluators below assume a well-formed expression, so check the grammar first:
+//   expr := term ((`+` | `*`) term)*
+//   term := digit | `(` expr `)`
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    let src = Source::new(18, input);
+    for line in input.lines() {
+        // Byte offsets of each non-space token, so that errors can point at them.
+        let tokens: Vec<(usize, char)> = line.char_indices().filter(|(_, c)| *c != ' ').collect();
+        let mut pos = 0;
+        validate_expr(&src, line, &tokens, &mut pos)?;
+        if let Some((i, c)) = tokens.get(pos) {
+            let message = if *c == ')' { "unmatched `)`" } else { "expected `+`, `*` or end of line" };
+            return Err(src.error(&line[*i..*i + c.len_utf8()], message));
+        }
+    }
+    Ok(())
+}
+
+fn validate_expr(src: &Source, line: &str, tokens: &[(usize, char)], pos: &mut usize) -> Result<(), ParseError> {
+    validate_term(src, line, tokens, pos)?;
+    while let Some((_, '+')) | Some((_, '*')) = tokens.get(*pos) {
+        *pos += 1;
+        validate_term(src, line, tokens, pos)?;
+    }
+    Ok(())
+}
+
+fn validate_term(src: &Source, line: &str, tokens: &[(usize, char)], pos: &mut usize) -> Result<(), ParseError> {
+    match tokens.get(*pos) {
+        Some((_, c)) if c.is_ascii_digit() => {
+            *pos += 1;
+            Ok(())
+        }
+        Some((i, '(')) => {
+            *pos += 1;
+            validate_expr(src, line, tokens, pos)?;
+            match tokens.get(*pos) {
+                Some((_, ')')) => {
+                    *pos += 1;
+                    Ok(())
+                }
+                _ => Err(src.error(&line[*i..*i + 1], "unclosed `(`")),
+            }
+        }
+        Some((i, c)) => Err(src.error(&line[*i..*i + c.len_utf8()], "expected a digit or `(`")),
+        None => Err(src.error(&line[line.len()..], "expected a digit or `(`")),
+    }
+}
 
 #[aoc(day18, part1)]
-pub fn solve_part1(input: &str) -> u64 {
-    input.lines().map(|l| {
+pub fn solve_part1(input: &str) -> Result<u64, ParseError> {
+    debug_span!("parse").in_scope(|| validate(input))?;
+    let _evaluate = debug_span!("evaluate").entered();
+    Ok(input.lines().map(|l| {
         let e = evaluate(&mut l.chars());
-        println!("{} = {}", l, e);
+        trace!("{} = {}", l, e);
         return e;
-    }).sum()
+    }).sum())
 }
 
 pub fn next_value(chars: &mut Chars<'_>) -> u64 {
@@ -127,7 +180,8 @@ pub fn run_op(op_stack: &mut Vec<Operator>, operand_stack: &mut Vec<u64>) -> boo
 }
 
 #[aoc(day18, part2)]
-pub fn solve_part2(input: &str) -> u64 {
-    input.lines().map(|l| op_evaluate(&mut l.chars())).sum()
+pub fn solve_part2(input: &str) -> Result<u64, ParseError> {
+    validate(input)?;
+    Ok(input.lines().map(|l| op_evaluate(&mut l.chars())).sum())
 }
 
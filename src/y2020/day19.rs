@@ -2,6 +2,8 @@
 // http://manishearth.github.io/blog/2017/01/10/rust-tidbits-box-is-special/
 // https://medium.com/@KevinHoffman/to-box-or-not-to-box-my-first-real-rust-refactor-db467119c4c7
 
+use crate::error::{ParseError, Source};
+use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
 use string_builder;
@@ -184,18 +186,40 @@ impl Op {
     }
 }
 
-pub fn parse_rules(rules_str: &str) -> HashMap<i32, Rule> {
-    rules_str
-    .lines()
-    .map(|l| {
-        let mut y = l.split(": ");
-        let rule_id = y.next().unwrap().parse::<i32>().unwrap();
-        let rule_def = y.next().unwrap();
-        let op = Op::parse(rule_def);
-        let rule_op = Rule { id: rule_id, op };
-        (rule_id, rule_op)
-    })
-    .collect()
+pub fn parse_rules(src: &Source, rules_str: &str) -> Result<HashMap<i32, Rule>, ParseError> {
+    lazy_static! {
+        static ref RULE_RE: Regex = Regex::new(r#"^(\d+): ("[^"]+"|\d+(?: \d+)*(?: \| \d+(?: \d+)*)*)$"#).unwrap();
+    }
+    let rules = rules_str
+        .lines()
+        .map(|l| {
+            let caps = src.captures(&RULE_RE, l, "`<id>: \"<literal>\"` or `<id>: <ids> | <ids>`")?;
+            let rule_id = src.parse::<i32>(caps.get(1).unwrap().as_str())?;
+            let op = Op::parse(caps.get(2).unwrap().as_str());
+            Ok((rule_id, Rule { id: rule_id, op }))
+        })
+        .collect::<Result<HashMap<i32, Rule>, ParseError>>()?;
+    // Every referenced rule must be defined somewhere, or build_lookup() will never compile it.
+    for l in rules_str.lines() {
+        let def = &l[l.find(": ").unwrap() + 2..];
+        if let Some(term) = def.split(' ').find(|t| t.parse::<i32>().is_ok_and(|id| !rules.contains_key(&id))) {
+            return Err(src.error(term, format!("rule {} is never defined", term)));
+        }
+    }
+    if !rules.contains_key(&0) {
+        return Err(src.error(rules_str, "expected a rule 0 to match messages against"));
+    }
+    Ok(rules)
+}
+
+// Returns the rules and messages sections.
+pub fn split_sections<'a>(src: &Source<'a>) -> Result<(&'a str, &'a str), ParseError> {
+    let mut sections = src.input.splitn(2, "\n\n");
+    let rules_str = sections.next().unwrap();
+    let examples_str = sections
+        .next()
+        .ok_or_else(|| src.missing("expected a blank line between the rules and the messages"))?;
+    Ok((rules_str, examples_str))
 }
 
 pub fn build_lookup(rules: &HashMap<i32, Rule>) -> HashMap<i32, String> {
@@ -225,26 +249,24 @@ pub fn get_regex(lookup: &HashMap<i32, String>, rule_id: i32) -> Regex {
 }
 
 #[aoc(day19, part1)]
-pub fn solve_part1(input: &str) -> i32 {
-    let mut sections = input.split("\n\n");
-    let rules_str = sections.next().unwrap();
-    let examples_str = sections.next().unwrap();
-    let rules: HashMap<i32, Rule> = parse_rules(rules_str);
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    let src = Source::new(19, input);
+    let (rules_str, examples_str) = split_sections(&src)?;
+    let rules: HashMap<i32, Rule> = parse_rules(&src, rules_str)?;
     let lookup = build_lookup(&rules);
     let zero_re = get_regex(&lookup, 0);
-    examples_str
+    Ok(examples_str
         .lines()
         .filter(|l| zero_re.is_match(*l))
-        .count() as i32
+        .count() as i32)
 }
 
 
 #[aoc(day19, part2)]
-pub fn solve_part2(input: &str) -> i32 {
-    let mut sections = input.split("\n\n");
-    let rules_str = sections.next().unwrap();
-    let examples_str = sections.next().unwrap();
-    let mut rules: HashMap<i32, Rule> = parse_rules(rules_str);
+pub fn solve_part2(input: &str) -> Result<i32, ParseError> {
+    let src = Source::new(19, input);
+    let (rules_str, examples_str) = split_sections(&src)?;
+    let mut rules: HashMap<i32, Rule> = parse_rules(&src, rules_str)?;
     rules.insert(8, Rule {id: 8, op: Op::rule(42, Some("+".to_string()))});
     rules.insert(11, Rule {
         id: 11,
@@ -257,8 +279,8 @@ pub fn solve_part2(input: &str) -> i32 {
     let lookup = build_lookup(&rules);
     let zero_re = get_regex(&lookup, 0);
 
-    examples_str
+    Ok(examples_str
         .lines()
         .filter(|l| zero_re.is_match(*l))
-        .count() as i32
+        .count() as i32)
 }
\ No newline at end of file
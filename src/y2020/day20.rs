@@ -1,8 +1,12 @@
+use crate::error::{ParseError, Source};
+use crate::grid::{Grid, Pos};
 use itertools::iproduct;
+use lazy_static::lazy_static;
+use regex::Regex;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fmt;
 use std::iter;
+use tracing::{debug_span, trace};
 
 const WILDCARD: char = ' ';
 const MARK: char = '#';
@@ -23,51 +27,60 @@ pub struct Tile {
     bottom: String,
     left: String,
     right: String,
-    data: Vec<String>, // TODO: HashMap<(i32, i32), char> is probably cleaner.
+    data: Grid<char>,
 }
 
 impl Tile {
-    pub fn from_data(id: TileId, data: Vec<String>) -> Tile {
-        // Kindof gross... we could compute these on the fly...
-        let top = data.first().unwrap().clone();
-        let bottom = data.last().unwrap().clone();
-        let mut iter = data.iter();
-        let mut left: String = String::new();
-        let mut right: String = String::new();
-        while let Some(line) = iter.next() {
-            left += &line[0..1].to_string();
-            right += &line[line.len() - 1..].to_string();
-        }
-        return Tile {
+    pub fn from_data(id: TileId, data: Grid<char>) -> Tile {
+        let (h, w) = (data.height(), data.width());
+        let row = |i: usize| (0..w).map(|j| data[(i, j)]).collect();
+        let col = |j: usize| (0..h).map(|i| data[(i, j)]).collect();
+        Tile {
             id,
-            top,
-            left,
-            right,
-            bottom,
+            top: row(0),
+            left: col(0),
+            right: col(w - 1),
+            bottom: row(h - 1),
             data,
-        };
+        }
     }
 
     // Map: tile_id -> Tile
-    pub fn parse_tiles(input: &str) -> HashMap<TileId, Tile> {
+    pub fn parse_tiles(input: &str) -> Result<HashMap<TileId, Tile>, ParseError> {
+        lazy_static! {
+            static ref HEADER_RE: Regex = Regex::new(r"^Tile (\d+):$").unwrap();
+        }
+        let src = Source::new(20, input);
         input
             .split("\n\n")
             .map(|x| {
-                let mut lines = x.lines();
-                let id_str = lines.next().unwrap().to_string(); // "Tile XXXX:"
-                let id = id_str[5..id_str.len() - 1].parse::<TileId>().unwrap();
-                let data: Vec<String> = lines.map(|s| s.to_string()).collect();
-                (id, Tile::from_data(id, data))
+                let header = x.lines().next().unwrap_or(x);
+                let caps = src.captures(&HEADER_RE, header, "`Tile <id>:`")?;
+                let id = src.parse::<TileId>(caps.get(1).unwrap().as_str())?;
+                let rows = x[header.len()..].trim_start_matches('\n');
+                let data = Grid::parse(&src, rows, "`#` or `.`", |c| match c {
+                    MARK | '.' => Some(c),
+                    _ => None,
+                })?;
+                if data.height() == 0 {
+                    return Err(src.error(header, "expected the tile's rows to follow"));
+                }
+                // Tiles are rotated, so they must be square.
+                if data.width() != data.height() {
+                    let row = rows.lines().next().unwrap();
+                    return Err(src.error(row, format!("expected a square tile of width {}", data.height())));
+                }
+                Ok((id, Tile::from_data(id, data)))
             })
             .collect()
     }
 
     pub fn width(&self) -> usize {
-        self.data.first().unwrap().len()
+        self.data.width()
     }
 
     pub fn height(&self) -> usize {
-        self.data.len()
+        self.data.height()
     }
 
     pub fn rev(s: &String) -> String {
@@ -75,27 +88,15 @@ impl Tile {
     }
 
     pub fn at(&self, i: usize, j: usize) -> char {
-        return self.data[i].as_bytes()[j] as char;
-    }
-
-    pub fn enumerate_chars(&self) -> impl Iterator<Item = ((usize, usize), char)> {
-        let x = self.clone(); // TODO: Correct way to specify lifetime, so we don't need this clone
-        iproduct!((0..x.height()), (0..x.width())).map(move |(i, j)| ((i, j), x.at(i, j)))
+        self.data[(i, j)]
     }
 
     pub fn rotate(&self) -> Tile {
-        let h = self.height();
-        let w = self.width();
-        Tile::from_data(
-            self.id,
-            (0..w)
-                .map(|j| (0..h).map(|i| self.at(h - i - 1, j)).collect())
-                .collect(),
-        )
+        Tile::from_data(self.id, self.data.rotate())
     }
 
     pub fn flip(&self) -> Tile {
-        Tile::from_data(self.id, self.data.iter().map(|s| Tile::rev(s)).collect())
+        Tile::from_data(self.id, self.data.flip())
     }
 
     pub fn rotations(&self) -> impl Iterator<Item = Tile> {
@@ -114,77 +115,48 @@ impl Tile {
 }
 
 
-struct Grid {
-    cells: HashMap<(usize, usize), char>,
-    width: usize,
-    height: usize,
+// The marked cells of `image` covered by some copy of `query` (in whichever orientation matches).
+fn find_all(image: &Grid<char>, query: &Grid<char>) -> Option<HashSet<Pos>> {
+    query.orientations().find_map(|q| {
+        let matched_cells: HashSet<Pos> = image
+            .positions()
+            .filter_map(|offset| find_at(image, &q, offset))
+            .flatten()
+            .collect();
+        if matched_cells.is_empty() {
+            return None;
+        }
+        Some(matched_cells)
+    })
 }
 
-impl Grid {
-    pub fn find_all(&self, query: &Tile) -> Option<HashSet<(usize, usize)>> {
-        query.orientations().find_map(|q| {
-            let matched_cells: HashSet<(usize, usize)> =
-                iproduct!((0..self.height), (0..self.width))
-                    .filter_map(|(i, j)| self.find_at(&q, (i, j)))
-                    .flat_map(|vec| vec)
-                    .collect();
-            if matched_cells.is_empty() {
-                return None;
+fn find_at(image: &Grid<char>, query: &Grid<char>, offset: Pos) -> Option<Vec<Pos>> {
+    query
+        .iter()
+        .filter(|(_, c)| **c != WILDCARD)
+        .map(move |((i, j), c)| {
+            let coord = (i + offset.0, j + offset.1);
+            match image.get(coord) {
+                Some(src_c) if src_c == c => Some(coord),
+                _ => None,
             }
-            Some(matched_cells)
         })
-    }
-
-    pub fn find_at(&self, query: &Tile, offset: (usize, usize)) -> Option<Vec<(usize, usize)>> {
-        query
-            .enumerate_chars()
-            .filter(|(_, c)| *c != WILDCARD)
-            .map(move |((i, j), c)| {
-                let coord = (i + offset.0, j + offset.1);
-                match self.cells.get(&coord) {
-                    Some(src_c) if *src_c == c => Some(coord),
-                    _ => None,
-                }
-            })
-            // http://xion.io/post/code/rust-iter-patterns.html
-            .collect()
-    }
-
-    pub fn from_tiles(tiles_arrangement: &HashMap<(usize, usize), Tile>) -> Grid {
-        let k = tiles_arrangement.keys().max().unwrap();
-        let dim_w = k.0 + 1;
-        let dim_h = k.1 + 1;
-        let t = &tiles_arrangement[k];
-        let tile_h = t.height() - 2 * FRAME;
-        let tile_w = t.width() - 2 * FRAME;
-        Grid {
-            height: dim_h * tile_h,
-            width: dim_w * tile_w,
-            cells: iproduct!((0..dim_h), (0..dim_w), (0..tile_h), (0..tile_w))
-                .map(|(y, x, i, j)| {
-                    let tile = &tiles_arrangement[&(y, x)];
-                    let coord = ((y * tile_h + i), (x * tile_w + j));
-                    (coord, tile.at(i + FRAME, j + FRAME))
-                })
-                .collect(),
-        }
-    }
+        // http://xion.io/post/code/rust-iter-patterns.html
+        .collect()
 }
 
-impl fmt::Debug for Grid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(
-            (0..self.height)
-                .map(|y| {
-                    (0..self.width)
-                        .map(|x| &self.cells[(&(y, x))])
-                        .collect::<String>()
-                })
-                .collect::<Vec<String>>()
-                .join("\n")
-                .as_str(),
-        )
-    }
+// The image made by stitching together the arranged tiles, without their frames.
+fn assemble(tiles_arrangement: &HashMap<(usize, usize), Tile>) -> Grid<char> {
+    let k = tiles_arrangement.keys().max().unwrap();
+    let dim_w = k.0 + 1;
+    let dim_h = k.1 + 1;
+    let t = &tiles_arrangement[k];
+    let tile_h = t.height() - 2 * FRAME;
+    let tile_w = t.width() - 2 * FRAME;
+    Grid::from_fn(dim_w * tile_w, dim_h * tile_h, |(i, j)| {
+        let tile = &tiles_arrangement[&(i / tile_h, j / tile_w)];
+        tile.at(i % tile_h + FRAME, j % tile_w + FRAME)
+    })
 }
 
 
@@ -272,25 +244,26 @@ impl Solver {
 }
 
 #[aoc(day20, part1)]
-pub fn solve_part1(input: &str) -> i64 {
-    let tiles_by_id = Tile::parse_tiles(input);
+pub fn solve_part1(input: &str) -> Result<i64, ParseError> {
+    let tiles_by_id = Tile::parse_tiles(input)?;
     let edge_map: HashMap<String, HashSet<TileId>> = Solver::collect_tiles_by_edge(&tiles_by_id);
-    Solver::find_corners(&edge_map).iter().product()
+    Ok(Solver::find_corners(&edge_map).iter().product())
 }
 
 #[aoc(day20, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    let tiles_by_id = Tile::parse_tiles(input);
-    let edge_map: HashMap<String, HashSet<TileId>> = Solver::collect_tiles_by_edge(&tiles_by_id);
-    let tiles_arrangement = Solver::layout_tiles(&edge_map, &tiles_by_id);
-    let g = Grid::from_tiles(&tiles_arrangement);
-    println!("Grid: {:?}", g);
-    let dragon = Tile::from_data(-1, DRAGON.iter().map(|s| s.to_string()).collect());
-    let dragon_cells = g.find_all(&dragon).unwrap();
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    let tiles_by_id = debug_span!("parse").in_scope(|| Tile::parse_tiles(input))?;
+    let g = debug_span!("layout_tiles").in_scope(|| {
+        let edge_map: HashMap<String, HashSet<TileId>> = Solver::collect_tiles_by_edge(&tiles_by_id);
+        let tiles_arrangement = Solver::layout_tiles(&edge_map, &tiles_by_id);
+        assemble(&tiles_arrangement)
+    });
+    trace!("Grid:\n{}", g);
+    let dragon = Grid::from_fn(DRAGON[0].len(), DRAGON.len(), |(i, j)| DRAGON[i].as_bytes()[j] as char);
+    let dragon_cells = debug_span!("find_all").in_scope(|| find_all(&g, &dragon)).unwrap();
     let non_dragon_cells = g
-        .cells
         .iter()
         .filter(|(coord, c)| **c == MARK && !dragon_cells.contains(coord))
         .count();
-    non_dragon_cells
+    Ok(non_dragon_cells)
 }
@@ -0,0 +1,82 @@
+use crate::error::{ParseError, Source};
+use crate::matching::{self, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+// How often each ingredient that can't contain an allergen appears, and the ingredients each allergen
+// could be in.
+pub type FoodData = (HashMap<String, i32>, HashMap<String, HashSet<String>>);
+
+// TODO: Break this down into saner steps
+pub fn get_data(input: &str) -> Result<FoodData, ParseError> {
+    lazy_static! {
+        static ref LINE_RE: Regex =
+            Regex::new(r"^(.+) \(contains (.+)\)$").unwrap();
+    }
+    let src = Source::new(21, input);
+    let mut non_allergen_ingredient_frequency: HashMap<String, i32> = HashMap::new();
+    let mut allergen_to_possible_ingredients: HashMap<String, HashSet<String>> = HashMap::new();
+    for line in input.lines() {
+        let caps = src.captures(&LINE_RE, line, "`<ingredients> (contains <allergens>)`")?;
+        let ingredients: HashSet<String> = caps[1].split(' ').map(|s| s.to_string()).collect();
+        let allergens: Vec<&str> = caps[2].split(", ").collect();
+        for allergen in allergens {
+            if !allergen_to_possible_ingredients.contains_key(allergen) {
+                let poss: HashSet<String> = ingredients.iter().map(|s| s.to_string()).collect();
+                allergen_to_possible_ingredients.insert(allergen.to_string(), poss);
+            } else {
+                allergen_to_possible_ingredients.get_mut(allergen).unwrap().retain(|k| ingredients.contains(k));
+            }
+        }
+        for ingredient in ingredients {
+            *non_allergen_ingredient_frequency.entry(ingredient.to_string()).or_insert(0) += 1;
+        }
+    }
+    for ings in allergen_to_possible_ingredients.values() {
+        for ing in ings {
+            non_allergen_ingredient_frequency.remove(&ing.to_string());
+        }
+    }
+    Ok((non_allergen_ingredient_frequency, allergen_to_possible_ingredients))
+}
+
+// Report why there's no answer as a problem with the input, at the first food listing an allergen that
+// can't be pinned down.
+fn unassignable_error(src: &Source, solution: &Solution<String, String>) -> ParseError {
+    let allergen = match solution {
+        Solution::Unique(_) => None,
+        Solution::Multiple(a, b) => a.iter().find(|(k, v)| b.get(*k) != Some(v)).map(|(k, _)| k),
+        Solution::Unsatisfiable(conflict) => conflict.keys.first(),
+    };
+    let message = format!("can't tell which ingredient has which allergen: {}", solution);
+    let line = allergen.and_then(|allergen| {
+        src.input.lines().find(|l| {
+            l.split_once("(contains ")
+                .is_some_and(|(_, allergens)| allergens.trim_end_matches(')').split(", ").any(|a| a == allergen))
+        })
+    });
+    match line {
+        Some(line) => src.error(line, message),
+        None => src.missing(message),
+    }
+}
+
+#[aoc(day21, part1)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    Ok(get_data(input)?.0.values().sum())
+}
+
+#[aoc(day21, part2)]
+pub fn solve_part2(input: &str) -> Result<String, ParseError> {
+    let allergen_to_ing = get_data(input)?.1;
+    let sol = match matching::solve(&allergen_to_ing) {
+        Solution::Unique(sol) => sol,
+        other => return Err(unassignable_error(&Source::new(21, input), &other)),
+    };
+    let mut sorted_allergens: Vec<String> = sol.keys().map(|s| s.clone()).collect();
+    sorted_allergens.sort();
+    let sol: Vec<&str> = sorted_allergens.iter().map(|a| sol.get(a).unwrap().as_str()).collect();
+    Ok(sol.join(","))
+}
\ No newline at end of file
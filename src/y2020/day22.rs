@@ -1,3 +1,6 @@
+use crate::error::{ParseError, Source};
+use lazy_static::lazy_static;
+use regex::Regex;
 use std::collections::VecDeque;
 use std::collections::HashSet;
 use std::collections::hash_map::DefaultHasher;
@@ -13,10 +16,24 @@ pub struct Outcome {
     score: i32,
 }
 
-pub fn parse_deck(input: &str) -> VecDeque<i32> {
+pub fn parse_deck(src: &Source, input: &str) -> Result<VecDeque<i32>, ParseError> {
+    lazy_static! {
+        static ref HEADER_RE: Regex = Regex::new(r"^Player \d+:$").unwrap();
+    }
     let mut lines = input.lines();
-    lines.next();
-    lines.map(|l| l.parse::<i32>().unwrap()).collect()
+    src.captures(&HEADER_RE, lines.next().unwrap_or(input), "`Player <n>:`")?;
+    lines.map(|l| src.parse::<i32>(l)).collect()
+}
+
+pub fn parse_decks(input: &str) -> Result<(VecDeque<i32>, VecDeque<i32>), ParseError> {
+    let src = Source::new(22, input);
+    let mut deck_str_iter = input.split("\n\n");
+    let deck1 = parse_deck(&src, deck_str_iter.next().unwrap())?;
+    let deck2 = parse_deck(
+        &src,
+        deck_str_iter.next().ok_or_else(|| src.missing("expected a blank line and a second deck"))?,
+    )?;
+    Ok((deck1, deck2))
 }
 
 pub fn score(deck: &VecDeque<i32>, depth: i32) -> i32 {
@@ -36,16 +53,14 @@ pub fn hash(deck1: &VecDeque<i32>, deck2: &VecDeque<i32>) -> u64 {
 
 
 #[aoc(day22, part1)]
-pub fn solve_part1(input: &str) -> i32 {
-    let mut deck_str_iter = input.split("\n\n");
-    let mut deck1 = parse_deck(deck_str_iter.next().unwrap());
-    let mut deck2 = parse_deck(deck_str_iter.next().unwrap());
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    let (mut deck1, mut deck2) = parse_decks(input)?;
     loop {
         if deck2.len() == 0 {
-            return score(&deck1, 0);
+            return Ok(score(&deck1, 0));
         }
         if deck1.len() == 0 {
-            return score(&deck2, 0);
+            return Ok(score(&deck2, 0));
         }
         let p1 = deck1.pop_front().unwrap();
         let p2 = deck2.pop_front().unwrap();
@@ -121,12 +136,8 @@ pub fn play_recursive_game(mut deck1: VecDeque<i32>, mut deck2: VecDeque<i32>, d
 }
 
 #[aoc(day22, part2)]
-pub fn solve_part2(input: &str) -> i32 {
-    let mut deck_str_iter = input.split("\n\n");
-    let outcome = play_recursive_game(
-        parse_deck(deck_str_iter.next().unwrap()),
-        parse_deck(deck_str_iter.next().unwrap()),
-        0
-    );
-    outcome.score
+pub fn solve_part2(input: &str) -> Result<i32, ParseError> {
+    let (deck1, deck2) = parse_decks(input)?;
+    let outcome = play_recursive_game(deck1, deck2, 0);
+    Ok(outcome.score)
 }
@@ -1,5 +1,25 @@
+use crate::error::{ParseError, Source};
 use std::char;
 
+// Cups are labelled with each of the digits 1..=n exactly once.
+pub fn parse_cups(input: &str) -> Result<Vec<u32>, ParseError> {
+    let src = Source::new(23, input);
+    let line = input.trim_end();
+    let mut cups: Vec<u32> = Vec::new();
+    for (j, c) in line.char_indices() {
+        let cup = c.to_digit(10).filter(|d| *d != 0);
+        match cup {
+            Some(d) if !cups.contains(&d) => cups.push(d),
+            Some(_) => return Err(src.error(&line[j..j + 1], format!("cup {} appears twice", c))),
+            None => return Err(src.error(&line[j..j + c.len_utf8()], "expected a cup label in 1-9")),
+        }
+    }
+    if let Some(d) = (1..=cups.len() as u32).find(|d| !cups.contains(d)) {
+        return Err(src.error(line, format!("expected cups labelled 1 to {}, but {} is missing", cups.len(), d)));
+    }
+    Ok(cups)
+}
+
 // Good enough for part1... but very slow.
 // NB: std::time::Instant can be used to time sections of code.
 pub fn naive_run(v: &mut Vec<u32>, idx: usize) -> usize {
@@ -40,7 +60,7 @@ pub fn naive_run(v: &mut Vec<u32>, idx: usize) -> usize {
 }
 
 #[derive(Debug)]
-struct Node<T> {
+pub struct Node<T> {
     // TODO: Can we model this as a doubly-linked list? It's complex :grimacing:
     // https://rust-unofficial.github.io/too-many-lists/fourth-final.html
     elem: T,
@@ -48,7 +68,7 @@ struct Node<T> {
     prev: T,
 }
 
-struct NodeRing {
+pub struct NodeRing {
     // TODO: Could we replace i64 with a generic?
     node_map: Vec<Node<i64>>,
     curr: i64,
@@ -62,7 +82,7 @@ impl NodeRing {
             let node = Node {
                 elem: *v,
                 next: vecs[(i + 1).rem_euclid(vecs.len())],
-                prev: vecs[(i - 1).rem_euclid(vecs.len())],
+                prev: vecs[(i + vecs.len() - 1).rem_euclid(vecs.len())],
             };
             node_map.push(node);
         }
@@ -147,43 +167,44 @@ impl NodeRing {
 }
 
 #[aoc(day23, part1)]
-pub fn solve_part1(input: &str) -> String {
-    let mut v: Vec<u32> = input.chars().map(|c| c.to_digit(10).unwrap()).collect();
+pub fn solve_part1(input: &str) -> Result<String, ParseError> {
+    let mut v: Vec<u32> = parse_cups(input)?;
     let mut idx = 0;
     for _ in 0..100 {
         idx = naive_run(&mut v, idx);
     }
     let one_idx = v.iter().position(|&r| r == 1).unwrap();
-    v[one_idx + 1..v.len()]
+    Ok(v[one_idx + 1..v.len()]
         .iter()
         .chain(v[0..one_idx].iter())
         .map(|d| char::from_digit(*d, 10).unwrap())
-        .collect()
+        .collect())
 }
 
 #[aoc(day23, part1, linked_vec)]
-pub fn solve_part1_linked_vec(input: &str) -> String {
-    let v: Vec<i64> = input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as i64)
+pub fn solve_part1_linked_vec(input: &str) -> Result<String, ParseError> {
+    let v: Vec<i64> = parse_cups(input)?
+        .into_iter()
+        .map(|d| d as i64)
         .collect();
     let mut l = NodeRing::new(v);
     for _ in 0..100 {
         l.run();
     }
-    l.get_list(1)
+    Ok(l.get_list(1)
         .iter()
         .skip(1) // output excludes the starting 1
         .map(|d| char::from_digit(*d as u32, 10).unwrap())
-        .collect()
+        .collect())
 }
 
 #[aoc(day23, part2, linked_vec)]
-pub fn solve_part2_linked_vec(input: &str) -> i64 {
-    let v: Vec<i64> = input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as i64)
-        .chain(10..1000001)
+pub fn solve_part2_linked_vec(input: &str) -> Result<i64, ParseError> {
+    let cups = parse_cups(input)?;
+    let v: Vec<i64> = cups
+        .iter()
+        .map(|d| *d as i64)
+        .chain((cups.len() as i64 + 1)..1000001)
         .collect();
     let mut l = NodeRing::new(v);
     for _ in 0..10000000 {
@@ -191,5 +212,5 @@ pub fn solve_part2_linked_vec(input: &str) -> i64 {
     }
     let c1 = l.get_next(1);
     let c2 = l.get_next(c1);
-    c1 * c2
+    Ok(c1 * c2)
 }
@@ -0,0 +1,67 @@
+use crate::automaton::{Automaton, Hex, Sparse};
+use crate::error::{ParseError, Source};
+use lazy_static::lazy_static;
+use num_complex::Complex;
+use regex::Regex;
+use std::collections::HashSet;
+
+// Tiles are hexagons in the doubled coordinates of `automaton::Hex`.
+// Position: starting tile.
+const O: Complex<i32> = Complex::new(0, 0);
+
+pub fn start_state(input: &str) -> Result<HashSet<Complex<i32>>, ParseError> {
+    lazy_static! {
+        static ref LINE_RE: Regex = Regex::new(r"w|e|nw|ne|sw|se").unwrap();
+    }
+    let src = Source::new(24, input);
+    let mut black_set: HashSet<Complex<i32>> = HashSet::new();
+    for line in input.lines() {
+        // Directions are back to back, so any gap between matches is something we don't understand.
+        let mut end = 0;
+        for mat in LINE_RE.find_iter(line) {
+            if mat.start() != end {
+                break;
+            }
+            end = mat.end();
+        }
+        if end != line.len() {
+            let c = line[end..].chars().next().unwrap();
+            return Err(src.error(&line[end..end + c.len_utf8()], "expected one of e, se, sw, w, nw or ne"));
+        }
+        let x = LINE_RE.find_iter(line).fold(O, |acc, text| {
+            acc + match text.as_str() {
+                "w" => Hex::W,
+                "e" => Hex::E,
+                "nw" => Hex::NW,
+                "ne" => Hex::NE,
+                "sw" => Hex::SW,
+                "se" => Hex::SE,
+                _ => panic!("unexpected direction"),
+            }
+        });
+        if black_set.contains(&x) {
+            black_set.remove(&x);
+        } else {
+            black_set.insert(x);
+        }
+    }
+    Ok(black_set)
+}
+
+// A black tile with 0 or more than 2 black neighbors flips to white, and a white tile with exactly
+// 2 black neighbors flips to black.
+fn rule(black: bool, n: usize) -> bool {
+    n == 2 || black && n == 1
+}
+
+#[aoc(day24, part1)]
+pub fn solve_part1(input: &str) -> Result<usize, ParseError> {
+    Ok(start_state(input)?.len())
+}
+
+#[aoc(day24, part2)]
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    let mut automaton = Sparse::new(Hex, rule, start_state(input)?);
+    automaton.run(100);
+    Ok(automaton.population())
+}
@@ -1,16 +1,27 @@
+use crate::error::{ParseError, Source};
+
 const MOD: i64 = 20201227;
 
 #[aoc(day25, part1)]
-pub fn solve_part1(input: &str) -> i64 {
+pub fn solve_part1(input: &str) -> Result<i64, ParseError> {
+    let src = Source::new(25, input);
     let mut iter = input.lines();
-    let pk1 = iter.next().unwrap().parse::<i64>().unwrap();
-    let pk2 = iter.next().unwrap().parse::<i64>().unwrap();
+    let mut next_key = || -> Result<i64, ParseError> {
+        let line = iter.next().ok_or_else(|| src.missing("expected two public keys"))?;
+        match src.parse::<i64>(line)? {
+            // Anything else never shows up in the loop below.
+            pk if (1..MOD).contains(&pk) => Ok(pk),
+            _ => Err(src.error(line, format!("public keys must be between 1 and {}", MOD - 1))),
+        }
+    };
+    let pk1 = next_key()?;
+    let pk2 = next_key()?;
     let l1 = compute_loop_size(7, pk1);
     let l2 = compute_loop_size(7, pk2);
     let e1 = transform(pk1, l2);
     let e2 = transform(pk2, l1);
     assert!(e1 == e2);
-    e1
+    Ok(e1)
 }
 
 
@@ -0,0 +1,182 @@
+use crate::error::{ParseError, Source};
+use crate::grid::Grid;
+use std::fmt;
+
+const TREE: char = '#';
+const OPEN: char = '.';
+
+/// Moving `right` columns for every `down` rows. `right` is an exact fraction, kept in lowest terms,
+/// so a slope can land between columns: a landing at column 2.5 is over the cell at column 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Slope {
+    numer: i64,
+    denom: i64,
+    down: usize,
+}
+
+/// The slopes part 2 checks.
+pub const SLOPES: [Slope; 5] = [
+    Slope::new(1, 1),
+    Slope::new(3, 1),
+    Slope::new(5, 1),
+    Slope::new(7, 1),
+    Slope::new(1, 2),
+];
+
+impl Slope {
+    pub const fn new(right: i64, down: usize) -> Slope {
+        assert!(down > 0, "a slope has to go down");
+        Slope {
+            numer: right,
+            denom: 1,
+            down,
+        }
+    }
+
+    /// `numer / denom` columns right for every `down` rows.
+    pub fn fraction(numer: i64, denom: i64, down: usize) -> Slope {
+        assert!(denom != 0 && down > 0, "a slope has to go down");
+        let gcd = gcd(numer, denom) * denom.signum();
+        Slope {
+            numer: numer / gcd,
+            denom: denom / gcd,
+            down,
+        }
+    }
+
+    /// The fraction of a column moved right each step, as numerator and (positive) denominator.
+    pub fn right(&self) -> (i64, i64) {
+        (self.numer, self.denom)
+    }
+
+    pub fn down(&self) -> usize {
+        self.down
+    }
+
+    /// Every distinct slope going `down` 1 to `max_down` rows a step, and right by a fraction in
+    /// `lo..=hi` with a denominator of at most `max_denom`. Sorted by `down`, then by how far right.
+    pub fn search(lo: i64, hi: i64, max_denom: i64, max_down: usize) -> Vec<Slope> {
+        let mut slopes: Vec<Slope> = (1..=max_down)
+            .flat_map(|down| {
+                (1..=max_denom).flat_map(move |denom| {
+                    (lo * denom..=hi * denom).map(move |numer| Slope::fraction(numer, denom, down))
+                })
+            })
+            .collect();
+        // Denominators are positive, so cross-multiplying keeps the order.
+        slopes.sort_by(|a, b| (a.down, a.numer * b.denom).cmp(&(b.down, b.numer * a.denom)));
+        slopes.dedup();
+        slopes
+    }
+
+    // The column after `steps` steps, rounded down to the cell it's over.
+    fn column(&self, steps: usize) -> i64 {
+        (steps as i64 * self.numer).div_euclid(self.denom)
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// e.g. `right 3 down 1`, or `right 1/2 down 1`.
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denom {
+            1 => write!(f, "right {} down {}", self.numer, self.down),
+            denom => write!(f, "right {}/{} down {}", self.numer, denom, self.down),
+        }
+    }
+}
+
+/// Where a slope lands on the way down, after the start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub slope: Slope,
+    /// Each landing as `(row, col)` on the map itself, so with the column wrapped around.
+    pub visited: Vec<(usize, usize)>,
+    pub trees: usize,
+}
+
+pub struct Map {
+    trees: Grid<bool>,
+}
+
+impl Map {
+    pub fn parse(input: &str) -> Result<Map, ParseError> {
+        let src = Source::new(3, input);
+        let trees = Grid::parse(&src, input, "`.` or `#`", |c| match c {
+            TREE => Some(true),
+            OPEN => Some(false),
+            _ => None,
+        })?;
+        Ok(Map { trees })
+    }
+
+    pub fn route(&self, slope: Slope) -> Route {
+        let width = self.trees.width() as i64;
+        let visited: Vec<(usize, usize)> = (1..)
+            .map(|step| (step * slope.down, slope.column(step)))
+            .take_while(|(i, _)| *i < self.trees.height())
+            .map(|(i, j)| (i, j.rem_euclid(width) as usize))
+            .collect();
+        let trees = visited.iter().filter(|pos| self.trees[**pos]).count();
+        Route {
+            slope,
+            visited,
+            trees,
+        }
+    }
+
+    fn traverse(&self, slope: Slope) -> i64 {
+        self.route(slope).trees as i64
+    }
+
+    /// The slope hitting the fewest trees, and its route. Ties go to the first slope.
+    pub fn fewest_trees(&self, slopes: &[Slope]) -> Option<Route> {
+        slopes
+            .iter()
+            .map(|s| self.route(*s))
+            .fold(None, |best: Option<Route>, r| match best {
+                Some(b) if b.trees <= r.trees => Some(b),
+                _ => Some(r),
+            })
+    }
+
+    /// The slope hitting the most trees, and its route. Ties go to the first slope.
+    pub fn most_trees(&self, slopes: &[Slope]) -> Option<Route> {
+        slopes
+            .iter()
+            .map(|s| self.route(*s))
+            .fold(None, |best: Option<Route>, r| match best {
+                Some(b) if b.trees >= r.trees => Some(b),
+                _ => Some(r),
+            })
+    }
+
+    /// The map drawn as in the puzzle, with each landing an `O`, or an `X` if it hit a tree.
+    pub fn render(&self, route: &Route) -> Grid<char> {
+        let mut grid = self.trees.map(|tree| if *tree { TREE } else { OPEN });
+        for pos in route.visited.iter() {
+            grid[*pos] = if self.trees[*pos] { 'X' } else { 'O' };
+        }
+        grid
+    }
+}
+
+#[aoc(day3, part1)]
+pub fn solve_part1(input: &str) -> Result<i64, ParseError> {
+    let map = Map::parse(input)?;
+    Ok(map.traverse(Slope::new(3, 1)))
+}
+
+
+#[aoc(day3, part2)]
+pub fn solve_part2(input: &str) -> Result<i64, ParseError> {
+    let map = Map::parse(input)?;
+    Ok(SLOPES.iter().map(|s| map.traverse(*s)).product())
+}
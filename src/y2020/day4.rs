@@ -1,10 +1,30 @@
+use crate::error::{ParseError, Source};
 use regex::Regex;
 use lazy_static::lazy_static;
 
 const TOTAL_FIELDS: i32 = 7;  // byr, ~cid~, ecl, eyr, hcl, hgt, iyr, pid
+const KNOWN_FIELDS: [&str; 8] = ["byr", "cid", "ecl", "eyr", "hcl", "hgt", "iyr", "pid"];
+
+// The solvers below only look at tokens their regexes match, so reject anything else up front
+// rather than silently skipping it.
+fn validate(input: &str) -> Result<(), ParseError> {
+    lazy_static! {
+        static ref TOKEN_RE: Regex = Regex::new(r"^(\w{3}):\S+$").unwrap();
+    }
+    let src = Source::new(4, input);
+    for token in input.split_whitespace() {
+        let caps = src.captures(&TOKEN_RE, token, "a `field:value` pair")?;
+        let field = caps.get(1).unwrap().as_str();
+        if !KNOWN_FIELDS.contains(&field) {
+            return Err(src.error(field, format!("unknown field `{}`", field)));
+        }
+    }
+    Ok(())
+}
 
 #[aoc(day4, part1, re_str)]
-pub fn solve_part4(input: &str) -> i32 {
+pub fn solve_part4(input: &str) -> Result<i32, ParseError> {
+    validate(input)?;
     lazy_static! {
         // Process each field:value token sucessively.
         // Two newlines in a row signals the end of a passport.
@@ -27,12 +47,13 @@ pub fn solve_part4(input: &str) -> i32 {
             fields_matched = 0;
         }
     }
-    return valid;
+    Ok(valid)
 }
 
 
 #[aoc(day4, part2)]
-pub fn solve_part2(input: &str) -> i32 {
+pub fn solve_part2(input: &str) -> Result<i32, ParseError> {
+    validate(input)?;
     lazy_static! {
         static ref PASSPORT_FIELD_RE: Regex = Regex::new(r"(.{3}):(\S+)(\n\n)?").unwrap();
         static ref HGT_RE: Regex = Regex::new(r"^(\d+)(in|cm)$").unwrap();
@@ -69,12 +90,12 @@ pub fn solve_part2(input: &str) -> i32 {
                     // If in, the number must be at least 59 and at most 76.
                     "in" => match &hcaps[1].parse::<i32>() {
                         Ok(hgt_in) => (59..77).contains(hgt_in),
-                        _ => panic!(),
+                        _ => false,
                     },
                     // If cm, the number must be at least 150 and at most 193.
                     "cm" => match &hcaps[1].parse::<i32>() {
                         Ok(hgt_cm) => (150..194).contains(hgt_cm),
-                        _ => panic!(),
+                        _ => false,
                     },
                     _ => panic!(),
                 },
@@ -86,7 +107,7 @@ pub fn solve_part2(input: &str) -> i32 {
             "ecl" => ECL_RE.is_match(value),
             // pid (Passport ID) - a nine-digit number, including leading zeroes.
             "pid" => PID_RE.is_match(value),
-            _ => panic!()
+            _ => unreachable!("validate() rejects unknown fields")
         };
         fields_matched += is_match as i32;
         // Avoid double counting a password that ends with `cid`
@@ -98,12 +119,13 @@ pub fn solve_part2(input: &str) -> i32 {
             fields_matched = 0;
         }
     }
-    return valid;
+    Ok(valid)
 }
 
 
 #[aoc(day4, part2, count)]
-pub fn solve_part2_count(input: &str) -> i32 {
+pub fn solve_part2_count(input: &str) -> Result<i32, ParseError> {
+    validate(input)?;
     lazy_static! {
         // NB: It looks like aoc_runner is stripping the trailing \n\n whitespace from `input`.
         static ref PASSPORT_RE: Regex = Regex::new(r"(?s)(.+?)(\n\n|$)").unwrap();
@@ -113,7 +135,7 @@ pub fn solve_part2_count(input: &str) -> i32 {
         static ref ECL_RE: Regex = Regex::new(r"^(amb|blu|brn|gry|grn|hzl|oth)$").unwrap();
         static ref PID_RE: Regex = Regex::new(r"^[0-9]{9}$").unwrap();
     }
-    PASSPORT_RE.captures_iter(input).filter(|caps| {
+    Ok(PASSPORT_RE.captures_iter(input).filter(|caps| {
         PASSPORT_FIELD_RE.captures_iter(&caps[1]).filter(|field_cap| {
             let field: &str = &field_cap[1];
             let value: &str = &field_cap[2];
@@ -141,12 +163,12 @@ pub fn solve_part2_count(input: &str) -> i32 {
                         // If in, the number must be at least 59 and at most 76.
                         "in" => match &hcaps[1].parse::<i32>() {
                             Ok(hgt_in) => (59..77).contains(hgt_in),
-                            _ => panic!(),
+                            _ => false,
                         },
                         // If cm, the number must be at least 150 and at most 193.
                         "cm" => match &hcaps[1].parse::<i32>() {
                             Ok(hgt_cm) => (150..194).contains(hgt_cm),
-                            _ => panic!(),
+                            _ => false,
                         },
                         _ => panic!(),
                     },
@@ -158,8 +180,8 @@ pub fn solve_part2_count(input: &str) -> i32 {
                 "ecl" => ECL_RE.is_match(value),
                 // pid (Passport ID) - a nine-digit number, including leading zeroes.
                 "pid" => PID_RE.is_match(value),
-                _ => panic!("unexpected field")
+                _ => unreachable!("validate() rejects unknown fields")
             }
         }).count() as i32 == TOTAL_FIELDS
-    }).count() as i32
+    }).count() as i32)
 }
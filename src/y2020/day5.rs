@@ -0,0 +1,143 @@
+use crate::error::{ParseError, Source};
+use itertools::sorted;
+use std::clone::Clone;
+use std::collections::HashSet;
+use std::iter::Iterator;
+use std::str;
+
+trait CloneIterator: Iterator + Clone {}
+
+const SEAT_WIDTH: usize = 10;
+
+/// Returns None if `seat` contains anything other than F, B, L or R.
+pub fn seat_to_id(seat: &str) -> Option<i32> {
+    // This is just a binary encoding described in a very roundabout way...
+    // BFFFBBFRRR --> 0b1000110111 --> 567
+    // FFFBBBFRRR --> 0b0001110111 --> 119
+    // BBFFBBFRLL --> 0b1100110100 --> 820
+    seat.chars().try_fold(0, |acc, b| {
+        Some(acc * 2
+            + match b {
+                'F' => 0,
+                'B' => 1,
+                'L' => 0,
+                'R' => 1,
+                _ => return None,
+            })
+    })
+}
+
+/// The inverse of `seat_to_id`, for ids in 0..1024.
+pub fn id_to_seat(id: i32) -> String {
+    (0..SEAT_WIDTH)
+        .rev()
+        .map(|bit| {
+            let set = id >> bit & 1 == 1;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
+
+fn parse_seat(src: &Source, seat: &str) -> Result<i32, ParseError> {
+    if let Some(j) = seat.find(|c| !matches!(c, 'F' | 'B' | 'L' | 'R')) {
+        let c = seat[j..].chars().next().unwrap();
+        return Err(src.error(&seat[j..j + c.len_utf8()], "expected one of F, B, L or R"));
+    }
+    if seat.len() != SEAT_WIDTH {
+        return Err(src.error(seat, format!("expected a seat of {} characters", SEAT_WIDTH)));
+    }
+    Ok(seat_to_id(seat).unwrap())
+}
+
+fn parse_seat_ids<B: std::iter::FromIterator<i32>>(input: &str) -> Result<B, ParseError> {
+    let src = Source::new(5, input);
+    input.lines().map(|l| parse_seat(&src, l)).collect()
+}
+
+fn no_seats(input: &str) -> ParseError {
+    Source::new(5, input).missing("expected at least one seat")
+}
+
+fn no_gap(input: &str) -> ParseError {
+    Source::new(5, input).missing("expected a free seat between two taken ones")
+}
+
+#[aoc(day5, part1)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
+    let seat_ids: Vec<i32> = parse_seat_ids(input)?;
+    seat_ids.into_iter().max().ok_or_else(|| no_seats(input))
+}
+
+#[aoc(day5, part1, vscan)]
+pub fn solve_part1_vscan(input: &str) -> Result<i32, ParseError> {
+    // The scan below indexes raw bytes, so make sure every row is a well-formed seat first.
+    let src = Source::new(5, input);
+    for l in input.lines() {
+        parse_seat(&src, l)?;
+    }
+    if input.lines().next().is_none() {
+        return Err(no_seats(input));
+    }
+    let data = input.as_bytes();
+    let width: usize = SEAT_WIDTH;
+    let rows = (data.len() + 1) / (width + 1);
+    let mut seat_idx: Vec<usize> = Vec::with_capacity(rows);
+    for i in 0..rows {
+        seat_idx.push(i);
+    }
+    let mut max_seat_id = 0;
+    for j in 0..width {
+        let new_seat_idxs: Vec<usize> = seat_idx
+            .iter()
+            .filter(|i| matches!(data[*i * (width + 1) + j], b'B' | b'R'))
+            .map(|u| *u)
+            .collect();
+        max_seat_id *= 2;
+        if new_seat_idxs.len() > 0 {
+            max_seat_id += 1;
+            seat_idx = new_seat_idxs;
+        }
+    }
+    Ok(max_seat_id)
+}
+
+#[aoc(day5, part2)]
+pub fn solve_part2(input: &str) -> Result<i32, ParseError> {
+    let mut seat_ids: Vec<i32> = parse_seat_ids(input)?;
+    seat_ids.sort();
+    let mut iter = seat_ids.iter().peekable();
+    while let Some(seat_id) = iter.next() {
+        if let Some(next_seat_id) = iter.peek() {
+            if seat_id + 2 == **next_seat_id {
+                return Ok(seat_id + 1);
+            }
+        }
+    }
+    Err(no_gap(input))
+}
+
+// This is slower than the above.
+#[aoc(day5, part2, hashset)]
+pub fn solve_part2_hashset(input: &str) -> Result<i32, ParseError> {
+    let taken: HashSet<i32> = parse_seat_ids(input)?;
+    (0..1024)
+        .find(|x| taken.contains(&(x - 1)) && !taken.contains(&x) && taken.contains(&(x + 1)))
+        .ok_or_else(|| no_gap(input))
+}
+
+// Basically as fast as solve_part2
+#[aoc(day5, part2, window)]
+pub fn solve_part2_window(input: &str) -> Result<i32, ParseError> {
+    let seat_ids: Vec<i32> = parse_seat_ids(input)?;
+    Ok(sorted(seat_ids)
+        .collect::<Vec<i32>>()
+        .windows(2)
+        .find(|w| w[1] - w[0] == 2)
+        .ok_or_else(|| no_gap(input))?[0]
+        + 1)
+}
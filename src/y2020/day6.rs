@@ -1,10 +1,24 @@
+use crate::error::{ParseError, Source};
 use regex::Regex;
 use lazy_static::lazy_static;
 use std::collections::HashSet;
 use std::collections::HashMap;
 
+// Each line is one person's answers, so anything but a-z is a typo (and would index out of
+// bounds in the `ch` variants).
+fn validate(input: &str) -> Result<(), ParseError> {
+    let src = Source::new(6, input);
+    for line in input.lines() {
+        if let Some((j, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(src.error(&line[j..j + c.len_utf8()], "expected an answer in a-z"));
+        }
+    }
+    Ok(())
+}
+
 #[aoc(day6, part1)]
-pub fn solve_part1(input: &str) -> usize {
+pub fn solve_part1(input: &str) -> Result<usize, ParseError> {
+    validate(input)?;
     lazy_static! {
         // NB: It looks like aoc_runner is stripping the trailing \n\n whitespace from `input`.
         static ref GROUP_RE: Regex = Regex::new(r"(?s)(.+?)(\n\n|$)").unwrap();
@@ -18,12 +32,13 @@ pub fn solve_part1(input: &str) -> usize {
         }
         sum += hash_set.len();
     }
-    sum
+    Ok(sum)
 }
 
  
 #[aoc(day6, part2)]
-pub fn solve_part2(input: &str) -> usize {
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    validate(input)?;
     lazy_static! {
         static ref GROUP_RE: Regex = Regex::new(r"(?s)(.+?)(\n\n|$)").unwrap();
         static ref ANSWER_RE: Regex = Regex::new(r"[a-z]").unwrap();
@@ -45,12 +60,13 @@ pub fn solve_part2(input: &str) -> usize {
         }
         sum += hash_map.iter().filter(|(_, v)| **v == people).count();
     }
-    sum
+    Ok(sum)
 }
 
 // 30x faster
 #[aoc(day6, part1, ch)]
-pub fn solve_part1_ch(input: &str) -> usize {
+pub fn solve_part1_ch(input: &str) -> Result<usize, ParseError> {
+    validate(input)?;
     let mut sum = 0;
     let mut counts: [i32; 26] = [0; 26];
     for line in input.lines().chain(std::iter::once("")) {
@@ -66,13 +82,14 @@ pub fn solve_part1_ch(input: &str) -> usize {
             }
         }
     }
-    sum
+    Ok(sum)
 }
 
 
 // 30x faster
 #[aoc(day6, part2, ch)]
-pub fn solve_part2_ch(input: &str) -> usize {
+pub fn solve_part2_ch(input: &str) -> Result<usize, ParseError> {
+    validate(input)?;
     let mut sum = 0;
     let mut counts: [i32; 26] = [0; 26];
     let mut people = 0;
@@ -91,5 +108,5 @@ pub fn solve_part2_ch(input: &str) -> usize {
             }
         }
     }
-    sum
+    Ok(sum)
 }
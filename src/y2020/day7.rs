@@ -1,3 +1,4 @@
+use crate::error::{ParseError, Source};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
@@ -6,22 +7,44 @@ use std::collections::HashSet;
 // https://hellocode.dev/rust-lifetimes
 // https://hellocode.dev/rust-ownership
 
-#[aoc(day7, part1)]
-pub fn solve_part1(input: &str) -> i32 {
+// Each rule is a parent bag and the (count, bag) pairs it must contain.
+type BagRule<'a> = (&'a str, Vec<(i64, &'a str)>);
+
+pub fn parse_rules(input: &str) -> Result<Vec<BagRule<'_>>, ParseError> {
     lazy_static! {
-        // Process each field:value token sucessively.
-        // Two newlines in a row signals the end of a passport.
-        static ref RULE_RE: Regex = Regex::new(r"(\w+ \w+) bags contain (.+\.)").unwrap();
-        static ref BAGS_RE: Regex = Regex::new(r"(\d+) (\w+ \w+) bags?[,.]").unwrap();
+        static ref RULE_RE: Regex = Regex::new(r"^(\w+ \w+) bags contain (.+)\.$").unwrap();
+        static ref BAGS_RE: Regex = Regex::new(r"^(\d+) (\w+ \w+) bags?$").unwrap();
     }
+    let src = Source::new(7, input);
+    input
+        .lines()
+        .map(|line| {
+            let caps = src.captures(&RULE_RE, line, "`<color> bags contain <contents>.`")?;
+            // https://stackoverflow.com/questions/51834111/lifetime-issue-iterating-over-regex-captures
+            let parent = caps.get(1).unwrap().as_str();
+            let child_str = caps.get(2).unwrap().as_str();
+            if child_str == "no other bags" {
+                return Ok((parent, Vec::new()));
+            }
+            let children = child_str
+                .split(", ")
+                .map(|child| {
+                    let ccaps = src.captures(&BAGS_RE, child, "`<count> <color> bag(s)`")?;
+                    let count = src.parse::<i64>(ccaps.get(1).unwrap().as_str())?;
+                    Ok((count, ccaps.get(2).unwrap().as_str()))
+                })
+                .collect::<Result<Vec<(i64, &str)>, ParseError>>()?;
+            Ok((parent, children))
+        })
+        .collect()
+}
+
+#[aoc(day7, part1)]
+pub fn solve_part1(input: &str) -> Result<i32, ParseError> {
     let mut hash_map: HashMap<&str, Vec<&str>> = HashMap::new();
-    for caps in RULE_RE.captures_iter(input) {
-        // https://stackoverflow.com/questions/51834111/lifetime-issue-iterating-over-regex-captures
-        let parent = caps.get(1).unwrap().as_str();
-        let child_str = caps.get(2).unwrap().as_str();
-        // NB: Empty bags will not enter this loop, as child_str will equal "no other bags."
-        for ccaps in BAGS_RE.captures_iter(child_str) {
-            let child = ccaps.get(2).unwrap().as_str();
+    for (parent, children) in parse_rules(input)? {
+        // NB: Empty bags will not enter this loop.
+        for (_, child) in children {
             if !hash_map.contains_key(child) {
                 hash_map.insert(child, Vec::new());
             }
@@ -49,17 +72,11 @@ pub fn solve_part1(input: &str) -> i32 {
             }
         }
     }
-    count
+    Ok(count)
 }
 
 #[aoc(day7, part2)]
-pub fn solve_part2(input: &str) -> i64 {
-    lazy_static! {
-        // Process each field:value token sucessively.
-        // Two newlines in a row signals the end of a passport.
-        static ref RULE_RE: Regex = Regex::new(r"(\w+ \w+) bags contain (.+\.)").unwrap();
-        static ref BAGS_RE: Regex = Regex::new(r"(\d+) (\w+ \w+) bags?[,.]").unwrap();
-    }
+pub fn solve_part2(input: &str) -> Result<i64, ParseError> {
     let mut bag_to_content_count: HashMap<&str, i64> = HashMap::new();
     let mut parent_to_unprocessed: HashMap<&str, i64> = HashMap::new();
     let mut child_to_parents: HashMap<&str, Vec<&str>> = HashMap::new();
@@ -73,18 +90,12 @@ pub fn solve_part2(input: &str) -> i64 {
     // For each child that has no unprocessed children, set to 1.
     // For each parent of that child, decrement a counter. If that counter hits 0, add it to the children list.
     // Repeat until all nodes are processed.
-    for caps in RULE_RE.captures_iter(input) {
-        // https://stackoverflow.com/questions/51834111/lifetime-issue-iterating-over-regex-captures
-        let parent = caps.get(1).unwrap().as_str();
-        let child_str = caps.get(2).unwrap().as_str();
-
+    for (parent, children) in parse_rules(input)? {
         parent_to_unprocessed.insert(parent, 0);
         parent_to_contents.insert(parent, Vec::new());
 
-        // NB: Empty bags will not enter this loop, as child_str will equal "no other bags."
-        for ccaps in BAGS_RE.captures_iter(child_str) {
-            let child = ccaps.get(2).unwrap().as_str();
-            let count = ccaps.get(1).unwrap().as_str().parse::<i64>().unwrap();
+        // NB: Empty bags will not enter this loop.
+        for (count, child) in children {
 
             if !child_to_parents.contains_key(child) {
                 child_to_parents.insert(child, Vec::new());
@@ -129,5 +140,8 @@ pub fn solve_part2(input: &str) -> i64 {
             }
         }
     }
-    *bag_to_content_count.get("shiny gold").unwrap()
+    // Bags that are part of a cycle, or hold a bag with no rule, never get counted.
+    bag_to_content_count.get("shiny gold").cloned().ok_or_else(|| {
+        Source::new(7, input).missing("expected `shiny gold` bags to hold a finite number of bags")
+    })
 }
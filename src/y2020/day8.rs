@@ -1,3 +1,4 @@
+use crate::error::{ParseError, Source};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashSet;
@@ -34,7 +35,8 @@ impl Program {
                 self.eip += 1;
             },
             Operator::JMP => {
-                self.eip += instr.argument as usize;
+                // Backwards jumps wrap around, as a negative offset.
+                self.eip = self.eip.wrapping_add(instr.argument as usize);
             },
         };
     }
@@ -68,43 +70,46 @@ impl Program {
         }
     }
 
-    pub fn parse(source: &str) -> Program {
+    pub fn parse(source: &str) -> Result<Program, ParseError> {
         lazy_static! {
-            static ref INSTR_RE: Regex = Regex::new(r"(acc|jmp|nop) ([+-]\d+)").unwrap();
+            static ref INSTR_RE: Regex = Regex::new(r"^(\w+) ([+-]\d+)$").unwrap();
         }
-        Program {
+        let src = Source::new(8, source);
+        Ok(Program {
             acc: 0,
             eip: 0,
-            instructions: INSTR_RE
-                .captures_iter(source)
-                .map(|caps| {
-                    let operator = match &caps[1] {
+            instructions: source
+                .lines()
+                .map(|line| {
+                    let caps = src.captures(&INSTR_RE, line, "`<operator> <+/-argument>`")?;
+                    let op = caps.get(1).unwrap().as_str();
+                    let operator = match op {
                         "acc" => Operator::ACC,
                         "jmp" => Operator::JMP,
                         "nop" => Operator::NOP,
-                        _ => panic!(),
+                        _ => return Err(src.error(op, format!("unknown operator `{}`", op))),
                     };
-                    let argument = *&caps[2].parse::<i64>().unwrap();
-                    Instruction { operator, argument }
+                    let argument = src.parse::<i64>(caps.get(2).unwrap().as_str())?;
+                    Ok(Instruction { operator, argument })
                 })
-                .collect(),
-        }
+                .collect::<Result<Vec<Instruction>, ParseError>>()?,
+        })
     }
 }
 
 
 #[aoc(day8, part1)]
-pub fn solve_part1(input: &str) -> i64 {
-    let program = &mut Program::parse(input);
+pub fn solve_part1(input: &str) -> Result<i64, ParseError> {
+    let program = &mut Program::parse(input)?;
     match program.run() {
-        Err(acc) => acc,
-        _ => panic!("Expected program to error"),
+        Err(acc) => Ok(acc),
+        _ => Err(Source::new(8, input).missing("expected the program to loop, but it finished")),
     }
 }
 
 #[aoc(day8, part2)]
-pub fn solve_part2(input: &str) -> i64 {
-    let program = Program::parse(input);
+pub fn solve_part2(input: &str) -> Result<i64, ParseError> {
+    let program = Program::parse(input)?;
     for (idx, instruction) in program.instructions.iter().enumerate() {
         let repl_program = &mut program.fork(
             idx,
@@ -118,8 +123,8 @@ pub fn solve_part2(input: &str) -> i64 {
             },
         );
         if let Ok(acc) = repl_program.run() {
-            return acc;
+            return Ok(acc);
         }
     }
-    panic!("Expected one of the program variants to exit successfully.");
+    Err(Source::new(8, input).missing("expected one jmp/nop swap to make the program finish"))
 }
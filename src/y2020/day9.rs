@@ -1,15 +1,18 @@
+use crate::error::{ParseError, Source};
 use std::collections::HashSet;
 use std::collections::LinkedList;
+use tracing::{debug, debug_span};
 
 const RUN: usize = 25;
 
 #[aoc(day9, part1)]
-pub fn solve_part1(input: &str) -> i32 {
-    let mut hash_set: HashSet<i32> = HashSet::new();
-    let mut list: LinkedList<i32> = LinkedList::new();
-    input
-        .lines()
-        .map(|l| { l.parse::<i32>().unwrap() })
+pub fn solve_part1(input: &str) -> Result<i64, ParseError> {
+    let src = Source::new(9, input);
+    let numbers: Vec<i64> = src.numbers().collect::<Result<_, _>>()?;
+    let mut hash_set: HashSet<i64> = HashSet::new();
+    let mut list: LinkedList<i64> = LinkedList::new();
+    numbers
+        .into_iter()
         .find(|x| {
             if list.len() >= RUN && list.iter().all(|a| !hash_set.contains(&(x - a))) {
                 return true;
@@ -21,20 +24,22 @@ pub fn solve_part1(input: &str) -> i32 {
                 hash_set.remove(&y);
             }
             return false;
-        }).unwrap()
+        }).ok_or_else(|| no_invalid_number(&src))
+}
+
+fn no_invalid_number(src: &Source) -> ParseError {
+    src.missing(format!("expected a number that isn't the sum of two of the {} before it", RUN))
 }
 
 
 #[aoc(day9, part2)]
-pub fn solve_part2(input: &str) -> i64 { 
-    let numbers: Vec<i64> = input
-        .lines()
-        .map(|l| { l.parse::<i64>().unwrap() })
-        .collect();
+pub fn solve_part2(input: &str) -> Result<i64, ParseError> {
+    let src = Source::new(9, input);
+    let numbers: Vec<i64> = debug_span!("parse").in_scope(|| src.numbers().collect::<Result<_, _>>())?;
 
     let mut hash_set: HashSet<i64> = HashSet::new();
     let mut list: LinkedList<i64> = LinkedList::new();    
-    let target = numbers.iter().find(|x| {
+    let target = debug_span!("find_target").in_scope(|| numbers.iter().find(|x| {
         if list.len() >= RUN && list.iter().all(|a| !hash_set.contains(&(*x - a))) {
             return true;
         }
@@ -45,9 +50,10 @@ pub fn solve_part2(input: &str) -> i64 {
             hash_set.remove(&y);
         }
         return false;
-    }).unwrap();
-    println!("Target: {}", target);
+    }).ok_or_else(|| no_invalid_number(&src)))?;
+    debug!("Target: {}", target);
 
+    let _find_run = debug_span!("find_run").entered();
     let mut first_iter = numbers.iter().enumerate();
     while let Some((i, v)) = first_iter.next() {
         let mut sum = *v;
@@ -58,10 +64,10 @@ pub fn solve_part2(input: &str) -> i64 {
             } 
             if sum == *target {
                 let s: Vec<i64> = (i..j + 1).map(|k| *numbers.get(k as usize).unwrap()).collect();
-                return *s.iter().min().unwrap() + *s.iter().max().unwrap()
+                return Ok(*s.iter().min().unwrap() + *s.iter().max().unwrap());
             }
         }
     }
-    panic!();
+    Err(src.missing(format!("expected a run of numbers adding up to {}", target)))
 }
 
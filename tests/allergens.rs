@@ -0,0 +1,17 @@
+// Allergens that can't be matched up with ingredients should say why rather than panic.
+
+use aoc_2020::y2020::day21;
+
+#[test]
+fn unassignable_allergens_point_at_a_food() {
+    let e = day21::solve_part2("a b (contains x, y)\nc (contains z)").unwrap_err();
+    assert_eq!((e.line, e.text.as_str()), (1, "a b (contains x, y)"));
+    assert!(e
+        .message
+        .starts_with("can't tell which ingredient has which allergen: ambiguous"));
+
+    let e =
+        day21::solve_part2("a b (contains x)\nb c (contains y)\nb (contains x, y)").unwrap_err();
+    assert_eq!(e.line, 1);
+    assert!(e.message.contains("unsatisfiable"));
+}
@@ -0,0 +1,68 @@
+// Both backends should agree wherever they can run the same automaton.
+
+use aoc_2020::automaton::{Automaton, Dense, Hex, Lattice, Sparse, Square};
+use num_complex::Complex;
+
+fn life(alive: bool, n: usize) -> bool {
+    n == 3 || alive && n == 2
+}
+
+#[test]
+fn blinker_oscillates_in_both_backends() {
+    let vertical: [(usize, usize); 3] = [(1, 2), (2, 2), (3, 2)];
+    let horizontal: [(usize, usize); 3] = [(2, 1), (2, 2), (2, 3)];
+
+    let mut sparse = Sparse::new(
+        Lattice::new(2),
+        life,
+        vertical.iter().map(|&(i, j)| vec![i as i32, j as i32]),
+    );
+    let cells = (0..5).flat_map(|i| (0..5).map(move |j| (i, j)));
+    let mut dense = Dense::new(
+        &Square::moore(5, 5),
+        life,
+        cells.map(|c| (c, vertical.contains(&c))),
+    );
+    for step in 0..4 {
+        let expected = if step % 2 == 0 { vertical } else { horizontal };
+        assert_eq!(sparse.population(), 3);
+        assert!(expected
+            .iter()
+            .all(|&(i, j)| sparse.alive().contains(&vec![i as i32, j as i32])));
+        assert!(expected.iter().all(|c| dense.is_alive(c)));
+        assert!(sparse.step());
+        assert!(dense.step());
+    }
+}
+
+#[test]
+fn still_lifes_settle_immediately() {
+    let block = [(0, 0), (0, 1), (1, 0), (1, 1)];
+    let mut dense = Dense::new(&Square::moore(4, 4), life, block.iter().map(|c| (*c, true)));
+    assert_eq!(dense.settle(), 0);
+    assert_eq!(dense.population(), 4);
+}
+
+#[test]
+fn custom_neighborhoods_are_topologies() {
+    // A ring of 5 cells, where a cell is alive next time if exactly one neighbor is.
+    let ring = |c: &usize| vec![(c + 1) % 5, (c + 4) % 5];
+    let mut dense = Dense::new(&ring, |_, n| n == 1, (0..5).map(|c| (c, c == 0)));
+    dense.step();
+    assert_eq!(
+        dense
+            .cells()
+            .filter(|(_, a)| *a)
+            .map(|(c, _)| *c)
+            .collect::<Vec<_>>(),
+        vec![1, 4]
+    );
+}
+
+#[test]
+fn hex_cells_have_six_neighbors() {
+    let mut sparse = Sparse::new(Hex, |_, n| n == 1, vec![Complex::new(0, 0)]);
+    sparse.step();
+    assert_eq!(sparse.population(), 6);
+    assert!(Hex::DIRS.iter().all(|d| sparse.alive().contains(d)));
+}
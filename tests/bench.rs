@@ -0,0 +1,109 @@
+// Benchmark statistics and reports, and solvers that fail while being timed.
+
+use aoc_2020::bench::{self, percentile, Config, Measurement, Report};
+use aoc_2020::error::ParseError;
+use aoc_2020::registry::{Answer, Solution};
+use std::time::Duration;
+
+fn measurement(day: u32, variant: Option<&str>, median_ns: u64) -> Measurement {
+    Measurement {
+        year: 2020,
+        day,
+        part: 1,
+        variant: variant.map(|v| v.to_string()),
+        samples: 3,
+        min_ns: median_ns / 2,
+        median_ns,
+        p90_ns: median_ns * 2,
+        p99_ns: median_ns * 2,
+        max_ns: median_ns * 3,
+        mean_ns: median_ns,
+    }
+}
+
+#[test]
+fn percentiles_use_the_nearest_rank() {
+    let sorted = [10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+    assert_eq!(percentile(&sorted, 0.0), 10);
+    assert_eq!(percentile(&sorted, 50.0), 50);
+    assert_eq!(percentile(&sorted, 90.0), 90);
+    assert_eq!(percentile(&sorted, 99.0), 100);
+    assert_eq!(percentile(&sorted, 100.0), 100);
+    assert_eq!(percentile(&[7], 50.0), 7);
+}
+
+#[test]
+fn comparisons_flag_regressions_past_the_threshold() {
+    let baseline = Report {
+        measurements: vec![measurement(1, None, 100), measurement(2, Some("fast"), 100)],
+    };
+    let current = Report {
+        measurements: vec![
+            measurement(1, None, 105),
+            measurement(2, Some("fast"), 150),
+            measurement(3, None, 100),
+        ],
+    };
+    let comparisons = current.compare(&baseline, 0.1);
+    let flagged: Vec<(u64, u64, bool)> = comparisons
+        .iter()
+        .map(|c| (c.baseline_ns, c.current_ns, c.regressed))
+        .collect();
+    // Day 3 has nothing to compare against.
+    assert_eq!(flagged, vec![(100, 105, false), (100, 150, true)]);
+}
+
+#[test]
+fn reports_round_trip_and_export() {
+    let report = Report {
+        measurements: vec![
+            measurement(1, Some("for_hash"), 100),
+            measurement(2, None, 40),
+        ],
+    };
+    let json = report.to_json();
+    assert_eq!(Report::from_json(&json).unwrap().to_json(), json);
+    // Reports from before there were seasons have no year.
+    let old = json.replace("\"year\": 2020,", "");
+    assert_eq!(Report::from_json(&old).unwrap().measurements[0].year, 2020);
+
+    let csv = report.to_csv();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[0].starts_with("year,day,part,variant,samples,"));
+    assert_eq!(rows[1], "2020,1,1,for_hash,3,50,100,200,200,300,100");
+    assert_eq!(rows[2], "2020,2,1,,3,20,40,80,80,120,40");
+}
+
+fn panics(_: &str) -> Result<Answer, ParseError> {
+    panic!("boom")
+}
+
+fn solves(input: &str) -> Result<Answer, ParseError> {
+    Ok(Answer::from(input.len()))
+}
+
+#[test]
+fn failing_solvers_are_skipped_not_fatal() {
+    let solution = |day, run| Solution {
+        year: 2020,
+        day,
+        part: 1,
+        variant: None,
+        run,
+    };
+    let solutions = [solution(1, panics as fn(&str) -> _), solution(2, solves)];
+    let config = Config {
+        warmup: 1,
+        samples: 2,
+        budget: Duration::from_secs(1),
+    };
+    let (report, skipped) = bench::run(solutions.iter(), |_, _| Some("abc".to_string()), &config);
+    assert_eq!(report.measurements.len(), 1);
+    assert_eq!(report.measurements[0].day, 2);
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(
+        (skipped[0].0.day, skipped[0].1.as_str()),
+        (1, "panicked: boom")
+    );
+}
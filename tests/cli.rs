@@ -0,0 +1,152 @@
+// The command line entry points, driven through the built binary.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn aoc(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc-2020"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Usage errors exit without reading stdin, so the pipe may already be closed.
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+const DAY1: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+#[test]
+fn run_reads_stdin() {
+    let out = aoc(&["run", "1", "--input", "-"], DAY1);
+    assert!(out.status.success());
+    let lines: Vec<String> = stdout(&out).lines().map(String::from).collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("y2020::day1::part1::for_hash: 514579 ("));
+    assert!(lines[1].starts_with("y2020::day1::part2::for_hash: 241861950 ("));
+}
+
+#[test]
+fn run_json_reports_answers_and_errors() {
+    let out = aoc(
+        &[
+            "run",
+            "1",
+            "2",
+            "--variant",
+            "for_loop",
+            "--input",
+            "-",
+            "--format",
+            "json",
+        ],
+        DAY1,
+    );
+    assert!(out.status.success());
+    let results: serde_json::Value = serde_json::from_str(&stdout(&out)).unwrap();
+    assert_eq!(results[0]["variant"], "for_loop");
+    assert_eq!(results[0]["answer"], 241861950);
+    assert!(results[0]["error"].is_null());
+
+    let out = aoc(
+        &["run", "8", "1", "--input", "-", "--format", "json"],
+        "nop +0\nxyz\n",
+    );
+    assert_eq!(out.status.code(), Some(1));
+    let results: serde_json::Value = serde_json::from_str(&stdout(&out)).unwrap();
+    assert!(results[0]["answer"].is_null());
+    assert_eq!(results[0]["error"]["kind"], "parse");
+    assert_eq!(results[0]["error"]["line"], 2);
+}
+
+#[test]
+fn unknown_variant_is_a_usage_error() {
+    let out = aoc(
+        &["run", "1", "1", "--variant", "nope", "--input", "-"],
+        DAY1,
+    );
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("for_hash, for_loop"));
+}
+
+#[test]
+fn diff_reports_each_part_with_variants() {
+    let out = aoc(&["diff", "1", "--input", "-"], DAY1);
+    assert!(out.status.success());
+    let text = stdout(&out);
+    assert!(text.contains("2020 Day 1 - Part 1: variants agree\n\tfor_hash       514579\n"));
+    assert!(text.contains("2020 Day 1 - Part 2: variants agree\n"));
+    assert!(!text.contains("input:"));
+}
+
+#[test]
+fn diff_fails_when_variants_disagree() {
+    // `for_hash` and `for_loop` will pair an entry up with itself, where `k_sum` only takes distinct ones.
+    let out = aoc(&["diff", "1", "1", "--input", "-"], "1010\n");
+    assert_eq!(out.status.code(), Some(1));
+    let text = stdout(&out);
+    assert!(text.starts_with("2020 Day 1 - Part 1: variants DISAGREE\n"));
+    assert!(text.contains("\tk_sum          rejected: "));
+    assert!(text.ends_with("\tinput:\n\t| 1010\n"));
+    assert!(!text.contains("Part 2"));
+
+    let out = aoc(&["diff", "25", "2", "--input", "-"], "");
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn generate_is_reproducible() {
+    let args = [
+        "generate", "5", "--seed", "7", "--size", "20", "--format", "json",
+    ];
+    let first: serde_json::Value = serde_json::from_str(&stdout(&aoc(&args, ""))).unwrap();
+    let second: serde_json::Value = serde_json::from_str(&stdout(&aoc(&args, ""))).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first["input"].as_str().unwrap().lines().count(), 20);
+
+    let out = aoc(
+        &["run", "5", "1", "--input", "-"],
+        first["input"].as_str().unwrap(),
+    );
+    assert!(stdout(&out).contains(&first["answers"]["1"].to_string()));
+}
+
+#[test]
+fn solvers_are_silent_unless_traced() {
+    let out = aoc(&["run", "9", "2"], "");
+    assert!(out.status.success());
+    assert!(out.stderr.is_empty());
+
+    let log = std::env::temp_dir().join(format!("aoc-2020-trace-{}.log", std::process::id()));
+    let out = aoc(
+        &[
+            "--trace",
+            "9",
+            "--trace-file",
+            log.to_str().unwrap(),
+            "run",
+            "9",
+            "2",
+        ],
+        "",
+    );
+    assert!(out.status.success());
+    assert!(out.stderr.is_empty());
+    let log_text = std::fs::read_to_string(&log).unwrap();
+    std::fs::remove_file(&log).unwrap();
+    assert!(log_text.contains("Target: 104054607"));
+    assert!(log_text.contains("find_run"));
+}
+
+#[test]
+fn unknown_year_is_a_usage_error() {
+    let out = aoc(&["--year", "2019", "run", "1"], "");
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("try one of: 2020"));
+}
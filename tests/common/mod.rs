@@ -0,0 +1,88 @@
+// A stand-in for the puzzle site: answers each request with the next canned response, and records
+// what it was sent.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub struct Server {
+    pub base_url: String,
+    pub requests: Arc<Mutex<Vec<Request>>>,
+}
+
+/// Serve `responses` (status, body) in order, one per connection, then stop.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> Server {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut fields = line.split_whitespace();
+            let (method, path) = (
+                fields.next().unwrap().to_string(),
+                fields.next().unwrap().to_string(),
+            );
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                match line.trim_end().split_once(": ") {
+                    Some((name, value)) => headers.push((name.to_string(), value.to_string())),
+                    None => break,
+                }
+            }
+            let length = headers
+                .iter()
+                .find(|(n, _): &&(String, String)| n.eq_ignore_ascii_case("content-length"))
+                .map_or(0, |(_, v)| v.parse().unwrap());
+            let mut body_bytes = vec![0; length];
+            reader.read_exact(&mut body_bytes).unwrap();
+            recorded.lock().unwrap().push(Request {
+                method,
+                path,
+                headers,
+                body: String::from_utf8(body_bytes).unwrap(),
+            });
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    Server { base_url, requests }
+}
+
+/// A fresh, empty directory for a test to use as its input cache.
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-2020-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,69 @@
+// Fetching inputs against a stand-in server.
+
+mod common;
+
+use aoc_2020::fetch::{FetchError, Fetcher};
+use aoc_2020::remote::{Remote, RemoteError};
+use std::fs;
+
+#[test]
+fn fetches_into_the_cache_once() {
+    let server = common::serve(vec![(200, "1721\n979\n")]);
+    let cache = common::temp_dir("fetch-once");
+    let fetcher = Fetcher::new(
+        Remote::new(&server.base_url, Some("abc123".to_string())),
+        cache.clone(),
+    );
+
+    assert_eq!(fetcher.get(2020, 1).unwrap(), "1721\n979\n");
+    assert_eq!(
+        fs::read_to_string(cache.join("2020/day1.txt")).unwrap(),
+        "1721\n979\n"
+    );
+    {
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2020/day/1/input");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert_eq!(requests[0].body, "");
+    }
+
+    // The server would hang up on a second request, so these must come from the cache.
+    assert_eq!(fetcher.get(2020, 1).unwrap(), "1721\n979\n");
+    assert!(matches!(fetcher.fetch(2020, 1), Err(FetchError::Cached(_))));
+    assert_eq!(server.requests.lock().unwrap().len(), 1);
+    fs::remove_dir_all(cache).unwrap();
+}
+
+#[test]
+fn failed_fetches_leave_nothing_cached() {
+    let server = common::serve(vec![(
+        400,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+    )]);
+    let cache = common::temp_dir("fetch-fail");
+    let fetcher = Fetcher::new(
+        Remote::new(&server.base_url, Some("expired".to_string())),
+        cache.clone(),
+    );
+
+    match fetcher.get(2020, 2) {
+        Err(FetchError::Remote(RemoteError::Status(400, body))) => assert!(body.contains("log in")),
+        other => panic!("expected a 400, got {:?}", other.map(|_| ())),
+    }
+    assert!(fetcher.cached(2020, 2).is_none());
+    assert_eq!(fs::read_dir(&cache).unwrap().count(), 0);
+    fs::remove_dir_all(cache).unwrap();
+}
+
+#[test]
+fn needs_a_session_to_fetch() {
+    let cache = common::temp_dir("fetch-anon");
+    let fetcher = Fetcher::new(Remote::new("http://127.0.0.1:9", None), cache.clone());
+    assert!(matches!(
+        fetcher.get(2020, 3),
+        Err(FetchError::Remote(RemoteError::NoSession))
+    ));
+    fs::remove_dir_all(cache).unwrap();
+}
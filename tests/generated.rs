@@ -0,0 +1,79 @@
+// Every variant of every day should agree on generated inputs, and match any answer the generator
+// planted.
+
+use aoc_2020::differential::{self, Outcome};
+use aoc_2020::generate::{self, Rng};
+use proptest::prelude::*;
+
+fn check(day: u32, parts: &[u32], seed: u64, size: usize) -> Result<(), TestCaseError> {
+    let generated = generate::generate(2020, day, size, &mut Rng::new(seed)).unwrap();
+    for part in parts.iter() {
+        let report = differential::check(2020, day, *part, &generated.input);
+        prop_assert!(report.agrees(), "{}", report);
+        let (_, outcome) = &report.outcomes[0];
+        match (generated.answer(*part), outcome) {
+            (Some(expected), Outcome::Solved(actual)) => prop_assert_eq!(expected, actual, "{}", report),
+            (None, Outcome::Solved(_)) => {}
+            _ => prop_assert!(false, "{}", report),
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    // Some of the solvers take a while in debug builds, so keep the inputs small and few.
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn day1(seed: u64, size in 1..300usize) { check(1, &[1, 2], seed, size)?; }
+    #[test]
+    fn day2(seed: u64, size in 1..300usize) { check(2, &[1, 2], seed, size)?; }
+    #[test]
+    fn day3(seed: u64, size in 1..300usize) { check(3, &[1, 2], seed, size)?; }
+    #[test]
+    fn day4(seed: u64, size in 1..300usize) { check(4, &[1, 2], seed, size)?; }
+    #[test]
+    fn day5(seed: u64, size in 1..900usize) { check(5, &[1, 2], seed, size)?; }
+    #[test]
+    fn day6(seed: u64, size in 1..300usize) { check(6, &[1, 2], seed, size)?; }
+    #[test]
+    fn day7(seed: u64, size in 1..300usize) { check(7, &[1, 2], seed, size)?; }
+    #[test]
+    fn day8(seed: u64, size in 1..300usize) { check(8, &[1, 2], seed, size)?; }
+    #[test]
+    fn day9(seed: u64, size in 1..300usize) { check(9, &[1, 2], seed, size)?; }
+    #[test]
+    fn day10(seed: u64, size in 1..100usize) { check(10, &[1, 2], seed, size)?; }
+    #[test]
+    fn day11(seed: u64, size in 1..10usize) { check(11, &[1, 2], seed, size)?; }
+    #[test]
+    fn day12(seed: u64, size in 1..300usize) { check(12, &[1, 2], seed, size)?; }
+    #[test]
+    fn day13(seed: u64, size in 1..10usize) { check(13, &[1, 2], seed, size)?; }
+    #[test]
+    fn day14(seed: u64, size in 1..50usize) { check(14, &[1, 2], seed, size)?; }
+    // Part 2 is 30 million turns whatever the input.
+    #[test]
+    fn day15(seed: u64, size in 1..8usize) { check(15, &[1], seed, size)?; }
+    #[test]
+    fn day16(seed: u64, size in 1..100usize) { check(16, &[1, 2], seed, size)?; }
+    #[test]
+    fn day17(seed: u64, size in 1..4usize) { check(17, &[1, 2], seed, size)?; }
+    #[test]
+    fn day18(seed: u64, size in 1..100usize) { check(18, &[1, 2], seed, size)?; }
+    #[test]
+    fn day19(seed: u64, size in 1..100usize) { check(19, &[1, 2], seed, size)?; }
+    #[test]
+    fn day20(seed: u64, size in 3..5usize) { check(20, &[1, 2], seed, size)?; }
+    #[test]
+    fn day21(seed: u64, size in 1..50usize) { check(21, &[1, 2], seed, size)?; }
+    #[test]
+    fn day22(seed: u64, size in 1..15usize) { check(22, &[1, 2], seed, size)?; }
+    // Part 2 is ten million moves whatever the input.
+    #[test]
+    fn day23(seed: u64, size in 5..10usize) { check(23, &[1], seed, size)?; }
+    #[test]
+    fn day24(seed: u64, size in 1..300usize) { check(24, &[1, 2], seed, size)?; }
+    #[test]
+    fn day25(seed: u64, size in 2..10_000usize) { check(25, &[1], seed, size)?; }
+}
@@ -0,0 +1,67 @@
+// The shared grid should behave the same however the puzzle got it.
+
+use aoc_2020::error::Source;
+use aoc_2020::grid::Grid;
+
+const PICTURE: &str = "ab\ncd\nef";
+
+fn picture() -> Grid<char> {
+    Grid::parse(&Source::new(0, PICTURE), PICTURE, "a letter", Some).unwrap()
+}
+
+#[test]
+fn parses_and_prints_back() {
+    let g = picture();
+    assert_eq!((g.width(), g.height()), (2, 3));
+    assert_eq!(g[(2, 1)], 'f');
+    assert_eq!(g.get((3, 0)), None);
+    assert_eq!(*g.get_wrapping(-1, 3), 'f');
+    assert_eq!(g.to_string(), PICTURE);
+}
+
+#[test]
+fn rejects_ragged_rows_and_unknown_cells() {
+    let input = "..\n.#.";
+    let e = Grid::parse(&Source::new(0, input), input, "`.`", Some).unwrap_err();
+    assert_eq!(
+        (e.line, e.message.as_str()),
+        (2, "expected a row of width 2")
+    );
+    let e = Grid::parse(&Source::new(0, input), input, "`.`", |c| {
+        if c == '.' {
+            Some(())
+        } else {
+            None
+        }
+    })
+    .unwrap_err();
+    assert_eq!(
+        (e.line, e.column, e.message.as_str()),
+        (2, 2, "expected `.`")
+    );
+}
+
+#[test]
+fn neighbors_stay_on_the_grid() {
+    let g = picture();
+    assert_eq!(
+        g.neighbors4((0, 0)).collect::<Vec<_>>(),
+        vec![(0, 1), (1, 0)]
+    );
+    assert_eq!(g.neighbors8((1, 0)).count(), 5);
+    assert_eq!(g.ray((0, 0), (1, 1)).collect::<Vec<_>>(), vec![(1, 1)]);
+    assert_eq!(g.ray((0, 1), (1, 0)).count(), 2);
+}
+
+#[test]
+fn rotations_and_flips() {
+    let g = picture();
+    assert_eq!(g.rotate().to_string(), "eca\nfdb");
+    assert_eq!(g.flip().to_string(), "ba\ndc\nfe");
+    assert_eq!(g.rotate().rotate().rotate().rotate(), g);
+    let orientations: Vec<Grid<char>> = g.orientations().collect();
+    assert_eq!(orientations.len(), 8);
+    assert!(orientations
+        .iter()
+        .all(|o| orientations.iter().filter(|p| p == &o).count() == 1));
+}
@@ -0,0 +1,252 @@
+// Properties that relate a day's fast solver to a slower (or inverse) one.
+
+use aoc_2020::interval::IntervalSet;
+use aoc_2020::matching::{self, Solution};
+use aoc_2020::y2020::day1::{closest_k_sum, find_k_sum, find_k_sum_with, subset_sum, Strategy as KSum};
+use aoc_2020::y2020::day13::{solve_chinese_remainder_theorem, solve_chinese_remainder_theorem_brute, ChineseRemainder};
+use aoc_2020::y2020::day23::{naive_run, NodeRing};
+use aoc_2020::y2020::day5::{id_to_seat, seat_to_id};
+use proptest::prelude::*;
+use proptest::sample::subsequence;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+const PRIMES: [i64; 15] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+// Pairwise coprime moduli, each with a remainder.
+fn congruences() -> impl Strategy<Value = Vec<(i64, i64)>> {
+    subsequence(PRIMES.to_vec(), 1..6)
+        .prop_flat_map(|ms| ms.into_iter().map(|m| (0..m, Just(m))).collect::<Vec<_>>())
+}
+
+// A field -> candidate columns map with exactly one solution, where some field always has a single
+// candidate left: the field of rank r fits the columns of rank <= r.
+fn staircase() -> impl Strategy<Value = (HashMap<usize, HashSet<usize>>, BTreeMap<usize, usize>)> {
+    (1..8usize)
+        .prop_flat_map(|n| (Just((0..n).collect::<Vec<usize>>()).prop_shuffle(), Just((0..n).collect::<Vec<usize>>()).prop_shuffle()))
+        .prop_map(|(field_by_rank, col_by_rank)| {
+            let constraints = field_by_rank
+                .iter()
+                .enumerate()
+                .map(|(rank, field)| (*field, col_by_rank[..=rank].iter().cloned().collect()))
+                .collect();
+            let solution = field_by_rank.iter().cloned().zip(col_by_rank.iter().cloned()).collect();
+            (constraints, solution)
+        })
+}
+
+// A field -> candidate columns map that has at least one solution, hidden among random extras.
+fn solvable() -> impl Strategy<Value = HashMap<usize, HashSet<usize>>> {
+    (1..7usize)
+        .prop_flat_map(|n| {
+            (
+                Just((0..n).collect::<Vec<usize>>()).prop_shuffle(),
+                prop::collection::vec(prop::collection::hash_set(0..n, 0..n), n),
+            )
+        })
+        .prop_map(|(hidden, extras)| {
+            hidden
+                .iter()
+                .zip(extras)
+                .enumerate()
+                .map(|(field, (col, mut cols))| {
+                    cols.insert(*col);
+                    (field, cols)
+                })
+                .collect()
+        })
+}
+
+// Any field -> candidate columns map, solvable or not.
+fn arbitrary_constraints() -> impl Strategy<Value = HashMap<usize, HashSet<usize>>> {
+    (1..6usize).prop_flat_map(|n| prop::collection::hash_map(0..n, prop::collection::hash_set(0..n + 1, 0..3), 1..=n))
+}
+
+// How many ways there are to assign every field a distinct column, counting no further than 2.
+fn count_naively(constraints: &[(usize, HashSet<usize>)], used: &mut HashSet<usize>) -> usize {
+    match constraints.split_first() {
+        None => 1,
+        Some(((_, cols), rest)) => {
+            let mut count = 0;
+            for col in cols.iter() {
+                if used.insert(*col) {
+                    count += count_naively(rest, used);
+                    used.remove(col);
+                }
+            }
+            count.min(2)
+        }
+    }
+}
+
+fn ranges() -> impl Strategy<Value = Vec<(i64, i64)>> {
+    prop::collection::vec((-20..20i64, 0..10i64).prop_map(|(lo, len)| (lo, lo + len)), 0..5)
+}
+
+// Every increasing tuple of k indices whose values add up to `target`, in order.
+fn k_sums_naively(values: &[i64], k: usize, target: i64, start: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return if target == 0 { vec![vec![]] } else { vec![] };
+    }
+    (start..values.len())
+        .flat_map(|i| {
+            k_sums_naively(values, k - 1, target - values[i], i + 1).into_iter().map(move |mut rest| {
+                rest.insert(0, i);
+                rest
+            })
+        })
+        .collect()
+}
+
+fn cups() -> impl Strategy<Value = Vec<u32>> {
+    (5..13u32).prop_flat_map(|n| Just((1..=n).collect::<Vec<u32>>()).prop_shuffle())
+}
+
+// The circle read clockwise from cup 1.
+fn from_one(cups: &[u32]) -> Vec<i64> {
+    let one = cups.iter().position(|c| *c == 1).unwrap();
+    cups[one..].iter().chain(cups[..one].iter()).map(|c| *c as i64).collect()
+}
+
+#[test]
+fn day1_k_sum_strategies_agree_past_the_crossover() {
+    // Enough values that `find_k_sum` picks meet-in-the-middle, with plenty of repeats and matches.
+    let values: Vec<i64> = (0..120i64).map(|i| (i * 37 + 11) % 61 - 30).collect();
+    assert_eq!(KSum::choose(4, values.len()), KSum::MeetInTheMiddle);
+    let expected = find_k_sum_with(&values, 4, 7, KSum::TwoPointer);
+    assert!(!expected.is_empty());
+    assert_eq!(find_k_sum(&values, 4, 7), expected);
+    assert_eq!(find_k_sum_with(&values, 4, 7, KSum::Hash), expected);
+}
+
+#[test]
+fn day1_sum_tables_only_cover_sums_that_can_reach_the_target() {
+    // Sums past the target can't come back down without negatives, however big the values are.
+    let big = [1_000_000_000, 3, 4, 2_000_000_000, 5];
+    assert_eq!(subset_sum(&big, 7), Some(vec![1, 2]));
+    assert_eq!(closest_k_sum(&big, 2, 10), Some((9, vec![2, 4])));
+    // With them, or at the extremes, the range is too wide to track, and that's a None too.
+    assert_eq!(subset_sum(&[1_000_000_000, -1_000_000_000, 5], 5), None);
+    assert_eq!(subset_sum(&[i64::MIN, i64::MAX, 1], 1), None);
+    assert_eq!(closest_k_sum(&[i64::MIN, i64::MIN, i64::MAX], 2, i64::MAX), None);
+    assert_eq!(closest_k_sum(&[-3, i64::MAX], 1, i64::MIN), None);
+}
+
+proptest! {
+    #[test]
+    fn day1_k_sum_strategies_find_every_tuple(
+        values in prop::collection::vec(-6..6i64, 0..12),
+        k in 0..5usize,
+        target in -10..10i64,
+    ) {
+        let expected = k_sums_naively(&values, k, target, 0);
+        for strategy in [KSum::Hash, KSum::TwoPointer, KSum::MeetInTheMiddle] {
+            prop_assert_eq!(find_k_sum_with(&values, k, target, strategy), expected.clone(), "{:?}", strategy);
+        }
+        prop_assert_eq!(find_k_sum(&values, k, target), expected);
+    }
+
+    #[test]
+    fn day1_subset_and_closest_sums_match_naive(
+        values in prop::collection::vec(-150..150i64, 0..9),
+        k in 0..4usize,
+        target in -500..500i64,
+    ) {
+        let exists = |k: usize, t: i64| !k_sums_naively(&values, k, t, 0).is_empty();
+        match subset_sum(&values, target) {
+            Some(indices) => {
+                prop_assert!(indices.windows(2).all(|w| w[0] < w[1]));
+                prop_assert_eq!(indices.iter().map(|i| values[*i]).sum::<i64>(), target);
+            }
+            None => prop_assert!((0..=values.len()).all(|k| !exists(k, target))),
+        }
+        let best = (-1400..=target).rev().find(|t| exists(k, *t));
+        match closest_k_sum(&values, k, target) {
+            Some((sum, indices)) => {
+                prop_assert_eq!(Some(sum), best);
+                prop_assert_eq!(indices.len(), k);
+                prop_assert!(indices.windows(2).all(|w| w[0] < w[1]));
+                prop_assert_eq!(indices.iter().map(|i| values[*i]).sum::<i64>(), sum);
+            }
+            None => prop_assert_eq!(None, best),
+        }
+    }
+
+    #[test]
+    fn day5_id_round_trips(id in 0..1024i32) {
+        prop_assert_eq!(seat_to_id(&id_to_seat(id)), Some(id));
+    }
+
+    #[test]
+    fn day5_seat_round_trips(seat in "[FB]{7}[LR]{3}") {
+        prop_assert_eq!(id_to_seat(seat_to_id(&seat).unwrap()), seat);
+    }
+
+    #[test]
+    fn day13_crt_matches_brute(crs in congruences()) {
+        let to_crs = || crs.iter().map(|(r, m)| ChineseRemainder { r: *r, m: *m });
+        let fast = solve_chinese_remainder_theorem(to_crs());
+        let brute = solve_chinese_remainder_theorem_brute(to_crs());
+        prop_assert_eq!((fast.r, fast.m), (brute.r, brute.m));
+        for (r, m) in crs.iter() {
+            prop_assert_eq!(fast.r.rem_euclid(*m), *r);
+        }
+    }
+
+    #[test]
+    fn day16_propagation_matches_brute((constraints, solution) in staircase()) {
+        prop_assert_eq!(matching::solutions(&constraints, 2), vec![solution.clone()]);
+        prop_assert_eq!(matching::propagate(&constraints).0, solution.clone());
+        prop_assert_eq!(matching::solve(&constraints), Solution::Unique(solution));
+    }
+
+    #[test]
+    fn day16_brute_finds_a_matching(constraints in solvable()) {
+        let sol = matching::solutions(&constraints, 1).pop().unwrap();
+        prop_assert_eq!(sol.len(), constraints.len());
+        prop_assert_eq!(sol.values().collect::<HashSet<_>>().len(), sol.len());
+        for (field, col) in sol.iter() {
+            prop_assert!(constraints[field].contains(col));
+        }
+    }
+
+    #[test]
+    fn matching_agrees_with_naive_count(constraints in arbitrary_constraints()) {
+        let listed: Vec<(usize, HashSet<usize>)> = constraints.clone().into_iter().collect();
+        match (matching::solve(&constraints), count_naively(&listed, &mut HashSet::new())) {
+            (Solution::Unique(sol), 1) => prop_assert_eq!(matching::solutions(&constraints, 2), vec![sol]),
+            (Solution::Multiple(a, b), 2) => prop_assert_ne!(a, b),
+            (Solution::Unsatisfiable(conflict), 0) => {
+                prop_assert!(conflict.values.len() < conflict.keys.len());
+                for field in conflict.keys.iter() {
+                    prop_assert!(constraints[field].iter().all(|col| conflict.values.contains(col)));
+                }
+            }
+            (solution, count) => prop_assert!(false, "{} but there are {} assignments", solution, count),
+        }
+    }
+
+    #[test]
+    fn interval_sets_match_their_ranges(a in ranges(), b in ranges()) {
+        let in_any = |ranges: &[(i64, i64)], v: i64| ranges.iter().any(|(lo, hi)| *lo <= v && v <= *hi);
+        let (x, y) = (IntervalSet::from_ranges(a.clone()), IntervalSet::from_ranges(b.clone()));
+        prop_assert!(x.ranges().windows(2).all(|w| w[0].1 + 1 < w[1].0));
+        for v in -25..35 {
+            prop_assert_eq!(x.contains(v), in_any(&a, v));
+            prop_assert_eq!(x.union(&y).contains(v), in_any(&a, v) || in_any(&b, v));
+            prop_assert_eq!(x.intersection(&y).contains(v), in_any(&a, v) && in_any(&b, v));
+            prop_assert_eq!(x.complement().contains(v), !in_any(&a, v));
+        }
+    }
+
+    #[test]
+    fn day23_naive_matches_node_ring(cups in cups(), moves in 0..300usize) {
+        let mut naive = cups.clone();
+        let mut idx = 0;
+        let mut ring = NodeRing::new(cups.iter().map(|c| *c as i64).collect());
+        for _ in 0..moves {
+            idx = naive_run(&mut naive, idx);
+            ring.run();
+        }
+        prop_assert_eq!(from_one(&naive), ring.get_list(1));
+    }
+}
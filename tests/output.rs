@@ -0,0 +1,72 @@
+// Run results should serialize the same way whichever command produced them.
+
+use aoc_2020::differential::Outcome;
+use aoc_2020::output::RunResult;
+use aoc_2020::registry::{self, Answer};
+use serde_json::json;
+use std::collections::HashSet;
+use std::time::Duration;
+
+#[test]
+fn answers_round_trip_as_plain_json() {
+    for (answer, value) in [
+        (Answer::Int(-3), json!(-3)),
+        (Answer::UInt(u64::MAX), json!(u64::MAX)),
+        (Answer::Text("abc".to_string()), json!("abc")),
+    ] {
+        assert_eq!(serde_json::to_value(&answer).unwrap(), value);
+        assert_eq!(serde_json::from_value::<Answer>(value).unwrap(), answer);
+    }
+}
+
+#[test]
+fn answers_compare_numbers_by_value() {
+    assert_eq!(Answer::from(5usize), Answer::from(5i32));
+    assert_eq!(Answer::UInt(5), Answer::Int(5));
+    assert_ne!(Answer::UInt(u64::MAX), Answer::Int(-1));
+    assert_ne!(Answer::Int(5), Answer::Text("5".to_string()));
+    let seen: HashSet<Answer> = [Answer::Int(5), Answer::UInt(5), Answer::Text("5".to_string())].into();
+    assert_eq!(seen.len(), 2);
+}
+
+#[test]
+fn errors_are_tagged_by_kind() {
+    let s = registry::find(2020, 8, 1, None).unwrap();
+    let rejected = Outcome::of(s, "nop +0\nxyz +1\n");
+    let result =
+        serde_json::to_value(RunResult::new(s, Some(&rejected), Duration::from_millis(1))).unwrap();
+    assert!(result["answer"].is_null());
+    assert_eq!(result["error"]["kind"], "parse");
+    assert_eq!(result["error"]["line"], 2);
+    assert_eq!(result["elapsed_ns"], 1_000_000);
+
+    let missing = serde_json::to_value(RunResult::new(s, None, Duration::default())).unwrap();
+    assert_eq!(missing["error"]["kind"], "missing_input");
+}
+
+#[test]
+fn inputs_without_an_answer_are_rejected_rather_than_panicking() {
+    let no_invalid: String = (1..=30).map(|n| format!("{}\n", n)).collect();
+    for (day, part, input) in [
+        (1, 1, "1\n2"),
+        (1, 1, "1000000000\n-1000000000"),
+        (1, 2, "1\n2\n3"),
+        (5, 1, ""),
+        (5, 2, "FBFBBFFRLR"),
+        (7, 2, "shiny gold bags contain 1 dark red bag.\ndark red bags contain 1 shiny gold bag."),
+        (8, 1, "nop +0\nacc +1"),
+        (8, 2, "jmp +0\njmp -1"),
+        (9, 1, no_invalid.as_str()),
+        (9, 2, no_invalid.as_str()),
+        (10, 1, "1\n5"),
+        (10, 2, "1\n5"),
+        (13, 1, "939\nx,x"),
+    ] {
+        for s in registry::variants(2020, day, part) {
+            match Outcome::of(s, input) {
+                Outcome::Rejected(e) => assert!(e.message.starts_with("expected"), "{}", e),
+                other => panic!("day {} part {} {:?}: {}", day, part, s.variant, other),
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Running solvers across a thread pool should give the same answers as running them one by one.
+
+use aoc_2020::differential::Outcome;
+use aoc_2020::parallel::{run_all, Table};
+use aoc_2020::registry::{self, Answer};
+use aoc_2020::verify::{Manifest, Status};
+use std::time::Duration;
+
+const DAY1: &str = "1721\n979\n366\n299\n675\n1456";
+
+fn day1() -> impl Iterator<Item = &'static registry::Solution> {
+    registry::solutions(2020).iter().filter(|s| s.day == 1)
+}
+
+#[test]
+fn timings_keep_the_given_order() {
+    let timings = run_all(day1(), |_, _| Some(DAY1.to_string()), 3);
+    assert_eq!(timings.len(), day1().count());
+    for (t, s) in timings.iter().zip(day1()) {
+        assert!(std::ptr::eq(t.solution, s));
+        let expected = if s.part == 1 { 514579 } else { 241861950 };
+        assert_eq!(t.outcome, Some(Outcome::Solved(Answer::Int(expected))));
+    }
+}
+
+#[test]
+fn table_is_sorted_by_cost_and_checks_the_manifest() {
+    let timings = run_all(day1(), |_, _| Some(DAY1.to_string()), 2);
+    let manifest = Manifest::parse(2020, "1 1 514579\n1 2 0\n").unwrap();
+    let table = Table::new(&timings, Some(&manifest), Duration::from_secs(1));
+    assert!(table.timings.windows(2).all(|w| w[0].cpu >= w[1].cpu));
+    for t in table.timings.iter() {
+        let expected = if t.solution.part == 1 { Status::Pass } else { Status::Fail };
+        assert_eq!(table.status(t), expected);
+    }
+    assert!(!table.ok());
+}
+
+#[test]
+fn missing_inputs_are_not_failures() {
+    let timings = run_all(day1(), |_, _| None, 2);
+    let table = Table::new(&timings, None, Duration::from_secs(1));
+    assert!(timings.iter().all(|t| t.outcome.is_none()));
+    assert!(table.timings.iter().all(|t| table.status(t) == Status::Missing));
+    assert!(table.ok());
+}
@@ -0,0 +1,102 @@
+// Password policies applied to the same parsed database.
+
+use aoc_2020::error::Source;
+use aoc_2020::y2020::day2::{
+    count_allowed, AllOf, Audit, CharClass, ForbiddenSubstrings, IgnoreCase, MinLength,
+    PasswordPolicy, Record, RequiredClasses, SledRental, Toboggan,
+};
+
+const SAMPLE: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+
+fn allowed(input: &str, policy: &dyn PasswordPolicy) -> Vec<bool> {
+    let records = Record::parse(&Source::new(2, input)).unwrap();
+    records.iter().map(|r| policy.allows(r)).collect()
+}
+
+#[test]
+fn puzzle_policies_share_a_parse() {
+    let records = Record::parse(&Source::new(2, SAMPLE)).unwrap();
+    assert_eq!(records[1].password, "cdefg");
+    assert_eq!(count_allowed(&records, &SledRental), 2);
+    assert_eq!(count_allowed(&records, &Toboggan), 1);
+}
+
+#[test]
+fn byte_parse_matches_str_parse() {
+    let input = "2-4 b: ébéé\n1-2 é: éé\n1-3 a: abcde";
+    let src = Source::new(2, input);
+    assert_eq!(Record::parse_bytes(&src).unwrap(), Record::parse(&src).unwrap());
+    let overflow = Source::new(2, "1-99999999999999999999 a: a");
+    assert_eq!(Record::parse_bytes(&overflow).unwrap_err().column, 3);
+    assert!(Record::parse_bytes(&Source::new(2, "1-3 a abc")).is_err());
+}
+
+#[test]
+fn counts_are_by_character() {
+    // `é` is two bytes, so counting bytes would put the `b` at position 3, and past the end is never a match.
+    assert_eq!(
+        allowed("2-4 b: ébéé\n1-2 é: éé\n1-2 x: éx\n3-9 x: éx", &Toboggan),
+        vec![true, false, true, false]
+    );
+    assert_eq!(allowed("2-2 é: éÉ", &SledRental), vec![false]);
+    assert_eq!(allowed("2-2 é: éÉ", &IgnoreCase(SledRental)), vec![true]);
+}
+
+#[test]
+fn extra_policies_combine() {
+    let input = "1-1 a: Passw0rd!\n1-1 a: password\n1-1 a: Sh0rt!\n1-1 a: Ünïcödé٣?";
+    let policy = AllOf(vec![
+        Box::new(MinLength(8)),
+        Box::new(RequiredClasses(vec![
+            CharClass::Uppercase,
+            CharClass::Lowercase,
+            CharClass::Digit,
+            CharClass::Symbol,
+        ])),
+        Box::new(ForbiddenSubstrings(vec!["ssw".to_string()])),
+    ]);
+    assert_eq!(allowed(input, &policy), vec![false, false, false, true]);
+}
+
+#[test]
+fn audit_explains_each_failure() {
+    let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-2 a: aaaaa\n2-8 a: abc";
+    let reasons = |audit: &Audit| -> Vec<Vec<String>> {
+        audit
+            .lines
+            .iter()
+            .map(|l| l.violations.iter().map(|v| v.to_string()).collect())
+            .collect()
+    };
+
+    let sled = Audit::parse(input, &SledRental).unwrap();
+    assert_eq!(
+        reasons(&sled)[1..4],
+        [
+            vec!["sled rental: 'b' appears 0 times, min 1".to_string()],
+            vec![],
+            vec!["sled rental: 'a' appears 5 times, max 2".to_string()],
+        ]
+    );
+    assert_eq!((sled.passed, sled.total), (2, 5));
+    assert_eq!(sled.by_letter[&'a'].passed, 1);
+    assert_eq!(sled.failures_by_policy["sled rental"], 3);
+
+    // Positions past the end used to panic.
+    let toboggan = Audit::parse(input, &Toboggan).unwrap();
+    assert_eq!(
+        reasons(&toboggan)[2..],
+        [
+            vec!["toboggan: positions 2 and 9 both match".to_string()],
+            vec!["toboggan: positions 1 and 2 both match".to_string()],
+            vec!["toboggan: position 8 is outside the 3 characters".to_string()],
+        ]
+    );
+    assert_eq!(
+        reasons(&toboggan)[1],
+        vec!["toboggan: neither position 1 nor 3 matches".to_string()]
+    );
+    let summary = "1 of 5 passed\n  toboggan failed 4\n  'a': 1 of 3 passed\n  'b': 0 of 1 passed\n  \
+                   'c': 0 of 1 passed\n";
+    assert!(toboggan.to_string().ends_with(summary));
+}
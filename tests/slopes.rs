@@ -0,0 +1,65 @@
+// Toboggan routes down the day 3 map, for whole and fractional slopes.
+
+use aoc_2020::y2020::day3::{Map, Slope, SLOPES};
+
+const SAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+#[test]
+fn whole_slopes_match_the_puzzle() {
+    let map = Map::parse(SAMPLE).unwrap();
+    let trees: Vec<usize> = SLOPES.iter().map(|s| map.route(*s).trees).collect();
+    assert_eq!(trees, vec![2, 7, 3, 4, 2]);
+
+    let route = map.route(Slope::new(3, 1));
+    assert_eq!(route.visited[..4], [(1, 3), (2, 6), (3, 9), (4, 1)]);
+    let drawn = map.render(&route).to_string();
+    let rows: Vec<&str> = drawn.lines().collect();
+    assert_eq!(
+        rows[..4],
+        ["..##.......", "#..O#...#..", ".#....X..#.", "..#.#...#O#"]
+    );
+}
+
+#[test]
+fn fractional_slopes_are_exact() {
+    let map = Map::parse(SAMPLE).unwrap();
+    let half = Slope::fraction(2, 4, 1);
+    assert_eq!(half, Slope::fraction(-1, -2, 1));
+    assert_eq!(
+        (half.right(), half.to_string()),
+        ((1, 2), "right 1/2 down 1".to_string())
+    );
+    // Landing at 0.5, 1, 1.5, 2, ... so over each column twice.
+    let route = map.route(half);
+    assert_eq!(route.visited[..5], [(1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]);
+    assert_eq!(
+        map.route(Slope::fraction(2, 1, 2)),
+        map.route(Slope::new(2, 2))
+    );
+    // Going left wraps around the other way.
+    assert_eq!(map.route(Slope::new(-1, 1)).visited[0], (1, 10));
+}
+
+#[test]
+fn search_finds_the_extremes() {
+    let map = Map::parse(SAMPLE).unwrap();
+    let slopes = Slope::search(0, 2, 3, 2);
+    // 0, 1/3, 1/2, 2/3, 1, 4/3, 3/2, 5/3 and 2, each going down 1 and 2.
+    assert_eq!(slopes.len(), 2 * 9);
+    let everything: Vec<usize> = slopes.iter().map(|s| map.route(*s).trees).collect();
+    let fewest = map.fewest_trees(&slopes).unwrap();
+    let most = map.most_trees(&slopes).unwrap();
+    assert_eq!(fewest.trees, *everything.iter().min().unwrap());
+    assert_eq!(most.trees, *everything.iter().max().unwrap());
+    assert_eq!(map.fewest_trees(&[]), None);
+}
@@ -0,0 +1,130 @@
+// Submitting answers against a stand-in server.
+
+mod common;
+
+use aoc_2020::remote::Remote;
+use aoc_2020::submit::{parse_wait, History, Refusal, SubmitError, Submitter, Verdict};
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+const RIGHT: &str = "<article><p>That's the right answer!  You are one gold star closer to saving your vacation.</p></article>";
+const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.</p></article>";
+const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>";
+
+#[test]
+fn parses_verdicts_and_waits() {
+    assert_eq!(Verdict::parse(RIGHT), Verdict::Accepted);
+    assert_eq!(Verdict::parse(TOO_HIGH), Verdict::TooHigh);
+    assert_eq!(Verdict::parse(TOO_RECENT), Verdict::Throttled);
+    assert_eq!(
+        Verdict::parse("<p>That's not the right answer.</p>"),
+        Verdict::Wrong
+    );
+    assert_eq!(parse_wait(TOO_HIGH), Some(Duration::from_secs(60)));
+    assert_eq!(parse_wait(TOO_RECENT), Some(Duration::from_secs(65)));
+    assert_eq!(
+        parse_wait("please wait 5 minutes before trying again"),
+        Some(Duration::from_secs(300))
+    );
+    assert_eq!(parse_wait(RIGHT), None);
+}
+
+#[test]
+fn records_attempts_and_never_resends_wrong_answers() {
+    let server = common::serve(vec![(200, TOO_HIGH), (200, RIGHT)]);
+    let dir = common::temp_dir("submit");
+    let history = History::load(dir.join("submissions.txt")).unwrap();
+    let mut submitter = Submitter::new(
+        Remote::new(&server.base_url, Some("abc123".to_string())),
+        history,
+    );
+
+    assert_eq!(
+        submitter.submit(2020, 13, 2, "1000").unwrap(),
+        Verdict::TooHigh
+    );
+    {
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2020/day/13/answer");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert_eq!(requests[0].body, "level=2&answer=1000");
+    }
+
+    // None of these reach the server.
+    let refusal = |r: Result<Verdict, SubmitError>| match r {
+        Err(SubmitError::Refused(refusal)) => refusal,
+        other => panic!("expected a refusal, got {:?}", other),
+    };
+    assert!(matches!(
+        refusal(submitter.submit(2020, 13, 2, "1000")),
+        Refusal::KnownWrong(_)
+    ));
+    assert!(matches!(
+        refusal(submitter.submit(2020, 13, 2, "1001")),
+        Refusal::KnownWrong(_)
+    ));
+    assert!(matches!(
+        refusal(submitter.submit(2020, 13, 2, "999")),
+        Refusal::TooSoon(_)
+    ));
+    assert_eq!(server.requests.lock().unwrap().len(), 1);
+
+    // Pretend the minute is up.
+    let history = fs::read_to_string(dir.join("submissions.txt")).unwrap();
+    let (time, rest) = history.split_once(' ').unwrap();
+    let earlier = time.parse::<u64>().unwrap() - 60;
+    fs::write(dir.join("submissions.txt"), format!("{} {}", earlier, rest)).unwrap();
+    let history = History::load(dir.join("submissions.txt")).unwrap();
+    assert_eq!(history.attempts[0].verdict, Verdict::TooHigh);
+    let mut submitter = Submitter::new(
+        Remote::new(&server.base_url, Some("abc123".to_string())),
+        history,
+    );
+
+    assert_eq!(
+        submitter.submit(2020, 13, 2, "999").unwrap(),
+        Verdict::Accepted
+    );
+    assert!(matches!(
+        refusal(submitter.submit(2020, 13, 2, "999")),
+        Refusal::AlreadyAccepted(_)
+    ));
+    assert_eq!(server.requests.lock().unwrap().len(), 2);
+    let log = History::load(dir.join("submissions.txt")).unwrap();
+    assert_eq!(log.attempts.len(), 2);
+    assert_eq!(log.attempts[1].answer, "999");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn submit_command_solves_then_submits() {
+    let server = common::serve(vec![(200, RIGHT)]);
+    let dir = common::temp_dir("submit-cli");
+    fs::create_dir_all(dir.join("input/2020")).unwrap();
+    fs::copy("input/2020/day13.txt", dir.join("input/2020/day13.txt")).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_aoc-2020"))
+        .args(["submit", "13", "2"])
+        .current_dir(&dir)
+        .env("AOC_BASE_URL", &server.base_url)
+        .env("AOC_SESSION", "abc123")
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("1010182346291467"));
+    assert!(stdout.contains("accepted"));
+    assert_eq!(
+        server.requests.lock().unwrap()[0].body,
+        "level=2&answer=1010182346291467"
+    );
+    let log = History::load(dir.join("input/submissions.txt")).unwrap();
+    assert_eq!(log.attempts[0].verdict, Verdict::Accepted);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,160 @@
+// Ticket rules, and fields that can't be pinned to columns, which should say why rather than panic.
+
+use aoc_2020::error::Source;
+use aoc_2020::matching;
+use aoc_2020::y2020::day16::{self, Exclusion, TicketData, Unassignable, ValidationReport};
+
+const AMBIGUOUS: &str = "a: 1-5 or 10-20
+b: 1-5 or 10-20
+departure c: 30-40 or 50-60
+
+your ticket:
+1,2,30
+
+nearby tickets:
+3,4,35";
+
+const UNSATISFIABLE: &str = "a: 1-5 or 7-8
+b: 1-5 or 7-8
+c: 1-20 or 30-40
+
+your ticket:
+1,2,3
+
+nearby tickets:
+1,2,3
+10,2,3
+1,10,4";
+
+fn assign(input: &str) -> Result<matching::Assignment<usize, usize>, Unassignable> {
+    let data = TicketData::parse(input).unwrap();
+    data.assign_fields(matching::solve(&data.to_constraints_map()))
+}
+
+#[test]
+fn ambiguous_fields_list_their_columns() {
+    let expected = vec![("a".to_string(), vec![0, 1]), ("b".to_string(), vec![0, 1])];
+    assert_eq!(assign(AMBIGUOUS), Err(Unassignable::Ambiguous(expected)));
+}
+
+#[test]
+fn unsatisfiable_fields_come_with_evidence() {
+    let exclusion = |ticket, column| Exclusion {
+        ticket,
+        column,
+        value: 10,
+        fields: vec!["a".to_string(), "b".to_string()],
+    };
+    let expected = Unassignable::Unsatisfiable {
+        fields: vec!["a".to_string(), "b".to_string()],
+        columns: vec![2],
+        evidence: vec![exclusion(1, 0), exclusion(2, 1)],
+    };
+    assert_eq!(assign(UNSATISFIABLE), Err(expected));
+}
+
+#[test]
+fn solvers_point_at_the_problem() {
+    for solve in [day16::solve_part2_no_brute, day16::solve_part2_brute] {
+        let e = solve(AMBIGUOUS).unwrap_err();
+        assert_eq!(e.line, 1);
+        assert!(e
+            .message
+            .starts_with("ambiguous: `a` could be in column 0 or 1"));
+
+        let e = solve(UNSATISFIABLE).unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (10, "10,2,3"));
+        assert!(e
+            .message
+            .starts_with("unsatisfiable: `a`, `b` only fit column 2"));
+    }
+}
+
+fn rules(text: &str) -> Result<Vec<String>, String> {
+    let src = Source::new(16, text);
+    match TicketData::parse_ranges(&src, text) {
+        Ok(ranges) => Ok(ranges
+            .iter()
+            .map(|r| format!("{}: {}", r.name, r.values))
+            .collect()),
+        Err(e) => Err(format!("{}:{}: {}", e.line, e.column, e.message)),
+    }
+}
+
+#[test]
+fn rules_combine_ranges_values_and_bounds() {
+    let text = "a: 1-3 or 5-7\nb: 1-3 or 2-5 or 9 or 10\nc: > 4 and <= 10 and not (6 or 7)\nd: not 5-10 or < 0";
+    assert_eq!(
+        rules(text),
+        Ok(vec![
+            "a: 1-3 or 5-7".to_string(),
+            "b: 1-5 or 9-10".to_string(),
+            "c: 5 or 8-10".to_string(),
+            "d: <= 4 or >= 11".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn bad_rules_point_at_the_problem() {
+    assert_eq!(
+        rules("a: 1-3 or"),
+        Err("1:10: expected a value".to_string())
+    );
+    assert_eq!(
+        rules("a: 1-3 4"),
+        Err("1:8: expected `or` or `and`".to_string())
+    );
+    assert_eq!(
+        rules("a: 5-3"),
+        Err("1:6: range ends before it starts at 5".to_string())
+    );
+    assert_eq!(rules("a: (1 or 2"), Err("1:11: expected `)`".to_string()));
+    assert_eq!(rules("a: 1 % 2"), Err("1:6: unexpected `%`".to_string()));
+}
+
+const SAMPLE: &str = "class: 1-3 or 5-7
+row, seat: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+#[test]
+fn validation_reports_each_ticket() {
+    let report = ValidationReport::parse(SAMPLE).unwrap();
+    let invalid: Vec<(usize, bool, Vec<usize>)> = report
+        .tickets
+        .iter()
+        .map(|t| (t.ticket, t.valid, t.invalid_columns.clone()))
+        .collect();
+    assert_eq!(
+        invalid,
+        vec![
+            (0, true, vec![]),
+            (1, false, vec![1]),
+            (2, false, vec![0]),
+            (3, false, vec![2]),
+        ]
+    );
+    assert_eq!(report.tickets[1].values[0].fields, vec!["row, seat", "seat"]);
+    assert_eq!(report.error_rate(), 71);
+    assert_eq!(day16::solve_part1(SAMPLE), Ok(71));
+
+    let csv = report.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 1 + 4 * 3);
+    assert_eq!(lines[0], "ticket,valid,column,value,fields");
+    assert_eq!(lines[1], "0,true,0,7,\"class;row, seat\"");
+    assert_eq!(lines[5], "1,false,1,4,");
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+    assert_eq!(json["tickets"][2]["invalid_columns"], serde_json::json!([0]));
+    assert_eq!(json["tickets"][2]["values"][2]["fields"], serde_json::json!(["seat"]));
+}